@@ -54,4 +54,26 @@ fn part2(rows: &[In]) -> Out {
     s
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    let mut s = String::new();
+    for _ in 0..size {
+        let len = 4 + rng.index(40);
+        let mut line = String::new();
+        while line.len() < len || !line.contains(|c: char| c.is_ascii_digit()) {
+            match rng.index(8) {
+                0 => line.push(char::from(b'1' + rng.index(9) as u8)),
+                1 => line += *rng.choose(&WORDS),
+                _ => line.push(char::from(b'a' + rng.index(26) as u8)),
+            }
+        }
+        s += &line;
+        s.push('\n');
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -59,4 +59,24 @@ fn part2(n: &[In]) -> Out {
     sum.try_into().unwrap()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut s = String::new();
+    for id in 1..=size {
+        let rounds = (0..1 + rng.index(6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let n = 1 + rng.index(3);
+                colors[..n]
+                    .iter()
+                    .map(|c| format!("{} {c}", 1 + rng.index(20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        s += &format!("Game {id}: {}\n", rounds.join("; "));
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -133,4 +133,30 @@ fn part2(grid: &In) -> Out {
     sum
 }
 
-util::register!(parse, part1, part2, @alt);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+    let mut s = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < size {
+            if !row.ends_with(|c: char| c.is_ascii_digit()) && rng.chance(0.15) {
+                let n = (1 + rng.below(999)).to_string();
+                if row.len() + n.len() <= size {
+                    row += &n;
+                    continue;
+                }
+            }
+            row.push(if rng.chance(0.08) {
+                *rng.choose(&SYMBOLS)
+            } else {
+                '.'
+            });
+        }
+        s += &row;
+        s.push('\n');
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
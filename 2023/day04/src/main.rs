@@ -40,4 +40,30 @@ fn part2(n: &[In]) -> Out {
     counts.iter().sum()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let nums = |v: &[N]| {
+        v.iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut s = String::new();
+    for id in 1..=size {
+        // keep the expected number of copies won per card below 1, or the counts explode
+        let matches = if rng.chance(0.7) { 0 } else { 1 + rng.index(3) };
+        let matches = matches.min(size - id);
+
+        let mut pool = (1..100).collect::<Vec<N>>();
+        rng.shuffle(&mut pool);
+        let win = &pool[..10];
+        let mut have = pool[10..35 - matches].to_vec();
+        have.extend(&win[..matches]);
+        rng.shuffle(&mut have);
+
+        s += &format!("Card {id:>3}: {} | {}\n", nums(win), nums(&have));
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
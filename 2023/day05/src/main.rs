@@ -140,4 +140,37 @@ fn part2(n: &In) -> Out {
     spans.into_iter().map(|s| s.start).min().unwrap()
 }
 
-util::register!(parse, part1, part2, @alt);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    const LIMIT: N = 1 << 32;
+    let size = size.max(1) as N;
+
+    let seeds = (0..size)
+        .flat_map(|_| [rng.below(LIMIT), 1 + rng.below(LIMIT / size / 4)])
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    let mut s = format!("seeds: {}\n", seeds.join(" "));
+
+    for header in MAPS {
+        s += &format!("\n{header} map:\n");
+        let mut cuts = (0..=size).map(|_| rng.below(LIMIT)).collect::<Vec<_>>();
+        cuts.sort();
+        cuts.dedup();
+        for w in cuts.windows(2) {
+            if rng.chance(0.8) {
+                s += &format!("{} {} {}\n", rng.below(LIMIT), w[0], w[1] - w[0]);
+            }
+        }
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
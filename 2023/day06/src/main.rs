@@ -0,0 +1,129 @@
+#![cfg_attr(test, feature(test))]
+
+use util::*;
+
+type N = u64;
+// type P = Pos<N>;
+
+struct Race {
+    time: N,
+    distance: N,
+}
+
+type In = Vec<Race>;
+type Out = N;
+
+fn parse(s: &'static str) -> In {
+    let l = s.lines().collect::<Vec<_>>();
+    let times = ints(l[0]);
+    let distances = ints(l[1]);
+    times
+        .iter()
+        .zip(distances)
+        .map(|(t, d)| Race {
+            time: *t,
+            distance: d,
+        })
+        .collect()
+}
+
+fn part1(n: &In) -> Out {
+    let mut result = 1;
+    for race in n {
+        let mut v = 0;
+        for hold in 0..race.time {
+            let go = race.time - hold;
+            let distance = go * hold;
+            if distance > race.distance {
+                v += 1;
+            }
+        }
+        result *= v;
+    }
+    result
+}
+
+fn part2(n: &In) -> Out {
+    let race_time = n
+        .iter()
+        .map(|r| r.time.to_string())
+        .collect::<String>()
+        .parse::<N>()
+        .unwrap();
+
+    let race_distance = n
+        .iter()
+        .map(|r| r.distance.to_string())
+        .collect::<String>()
+        .parse::<N>()
+        .unwrap();
+
+    let mut min = N::MAX;
+    for i in 0..race_time {
+        if i * (race_time - i) > race_distance {
+            min = i;
+            break;
+        }
+    }
+    let mut max = 0;
+    for i in min..race_time {
+        if i * (race_time - i) < race_distance {
+            max = i;
+            break;
+        }
+    }
+
+    (min..max).count() as N
+}
+
+// splits `digits` into `k` numbers, none starting with a 0
+fn split_digits(rng: &mut rng::Rng, digits: &str, k: usize) -> Option<Vec<N>> {
+    let mut cuts = (1..digits.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut cuts);
+    let mut cuts = cuts[..k.checked_sub(1)?.min(cuts.len())].to_vec();
+    cuts.sort();
+    cuts.push(digits.len());
+
+    let mut prev = 0;
+    let mut out = vec![];
+    for cut in cuts {
+        let piece = &digits[prev..cut];
+        if piece.starts_with('0') {
+            return None;
+        }
+        out.push(p(piece));
+        prev = cut;
+    }
+    (out.len() == k).then_some(out)
+}
+
+// `size` is roughly the concatenated race time, which part 2 scans linearly. races are
+// re-rolled until the concatenated race can be won too, as in the real input
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let total = size.max(10) as N + rng.below(size as N / 10 + 1);
+    let digits = total.to_string();
+
+    let concat = |v: &[N]| p::<N>(&v.iter().map(N::to_string).collect::<String>());
+    let (times, distances) = loop {
+        let k = 1 + rng.index(4.min(digits.len()));
+        let Some(times) = split_digits(rng, &digits, k) else {
+            continue;
+        };
+        if times.iter().any(|&t| t < 3) {
+            continue;
+        }
+        let distances = times.iter().map(|&t| {
+            let hold = t / 4 + rng.below(t / 4 + 1);
+            (hold * (t - hold)).saturating_sub(1)
+        });
+        let distances = distances.collect::<Vec<_>>();
+        if concat(&distances) < total * total / 4 {
+            break (times, distances);
+        }
+    };
+
+    let row = |v: &[N]| v.iter().map(|n| format!(" {n:>4}")).collect::<String>();
+    format!("Time:    {}\nDistance:{}\n", row(&times), row(&distances))
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
@@ -70,6 +70,25 @@ fn joker_score(hand: [N; 5]) -> N {
     options.into_iter().map(score).max().unwrap()
 }
 
+#[cfg(test)]
+fn joker_score_counting(mut hand: [N; 5]) -> N {
+    let mut counts = BTreeMap::<N, N>::new();
+    for card in hand.iter().filter(|c| **c != 0) {
+        *counts.entry(*card).or_default() += 1;
+    }
+    // jokers always do best by copying the most common card
+    let best = counts
+        .into_iter()
+        .max_by_key(|(card, count)| (*count, *card))
+        .map_or(14, |(card, _)| card);
+    for card in &mut hand {
+        if *card == 0 {
+            *card = best;
+        }
+    }
+    score(hand)
+}
+
 fn part1(n: &[In]) -> Out {
     let mut hands = n.to_vec();
     hands.sort_by_key(|h| (score(h.0), h.0));
@@ -98,4 +117,32 @@ fn part2(n: &[In]) -> Out {
         .sum()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+
+    let mut s = String::new();
+    for _ in 0..size {
+        let hand = (0..5)
+            .map(|_| char::from(*rng.choose(CARDS)))
+            .collect::<String>();
+        s += &format!("{hand} {}\n", 1 + rng.below(1000));
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
+
+#[cfg(test)]
+#[test]
+fn test_joker_score_differential() {
+    use util::diff::{self, Shrink};
+
+    const CARDS: [N; 13] = [0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 13, 14];
+    diff::check(
+        2000,
+        |rng| [(); 5].map(|_| *rng.choose(&CARDS)),
+        Shrink::shrink,
+        |hand| joker_score(*hand),
+        |hand| joker_score_counting(*hand),
+    );
+}
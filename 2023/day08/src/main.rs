@@ -0,0 +1,202 @@
+#![cfg_attr(test, feature(test))]
+
+use util::*;
+
+type C3 = [char; 3];
+type In = (Vec<bool>, HashMap<C3, (C3, C3)>);
+type Out = u64;
+
+fn parse(s: &'static str) -> In {
+    let mut lines = s.lines();
+
+    let instructions = lines.next().unwrap().chars().map(|c| c == 'R').collect();
+    lines.next();
+
+    let network = lines
+        .map(|l| {
+            let cs = l
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<Vec<_>>();
+
+            (
+                cs[..3].try_into().unwrap(),
+                (cs[3..6].try_into().unwrap(), cs[6..].try_into().unwrap()),
+            )
+        })
+        .collect();
+
+    (instructions, network)
+}
+
+fn solve(mut v: C3, (instructions, network): &In, f: impl Fn(C3) -> bool) -> Out {
+    let mut i = 0;
+    loop {
+        for &right in instructions {
+            i += 1;
+            let (l, r) = network[&v];
+            v = if right { r } else { l };
+            if f(v) {
+                return i;
+            }
+        }
+    }
+}
+
+fn part1(n: &In) -> Out {
+    solve(['A'; 3], n, |v| v == ['Z'; 3])
+}
+
+/// When a ghost stands on a Z node: at the listed times before it settles into a loop, then
+/// at each of `cycle` plus any multiple of `period`.
+struct Ghost {
+    lead_in: Vec<Out>,
+    cycle: Vec<Out>,
+    period: Out,
+}
+
+impl Ghost {
+    fn trace(mut v: C3, (instructions, network): &In) -> Self {
+        let mut seen = HashMap::new();
+        let mut hits = vec![];
+        let mut t = 0;
+        let start = loop {
+            let i = t as usize % instructions.len();
+            if let Some(&start) = seen.get(&(v, i)) {
+                break start;
+            }
+            seen.insert((v, i), t);
+
+            let (l, r) = network[&v];
+            v = if instructions[i] { r } else { l };
+            t += 1;
+            if v[2] == 'Z' {
+                hits.push(t);
+            }
+        };
+
+        // hits from before the loop was entered never come back
+        let (lead_in, cycle) = hits.into_iter().partition(|&h| h < start);
+        Ghost {
+            lead_in,
+            cycle,
+            period: t - start,
+        }
+    }
+
+    fn at_z(&self, t: Out) -> bool {
+        self.lead_in.contains(&t)
+            || self
+                .cycle
+                .iter()
+                .any(|&c| t >= c && (t - c).is_multiple_of(self.period))
+    }
+}
+
+fn part2(n: &In) -> Out {
+    let ghosts =
+        n.1.keys()
+            .copied()
+            .filter(|v| v[2] == 'A')
+            .map(|v| Ghost::trace(v, n))
+            .collect::<Vec<_>>();
+
+    // a ghost still finding its loop can only line up with the rest at one of its own hits
+    let mut early = ghosts.iter().flat_map(|g| &g.lead_in).collect::<Vec<_>>();
+    early.sort();
+    if let Some(&&t) = early.iter().find(|&&&t| ghosts.iter().all(|g| g.at_z(t))) {
+        return t;
+    }
+
+    // otherwise every ghost is looping, so try each way of picking one hit per loop
+    let mut choices: Vec<Vec<(i64, i64)>> = vec![vec![]];
+    for ghost in &ghosts {
+        let period = ghost.period as i64;
+        choices = choices
+            .iter()
+            .flat_map(|prev| {
+                ghost
+                    .cycle
+                    .iter()
+                    .map(|&c| [&prev[..], &[(c as i64, period)]].concat())
+            })
+            .collect();
+    }
+    choices
+        .iter()
+        .filter_map(|cycles| math::first_common_time(cycles))
+        .min()
+        .expect("the ghosts never line up") as Out
+}
+
+// `size` is the length of the instructions; each ghost loops over a multiple of it. three
+// character names run out a little above size 1400
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const ALNUM: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    const PRIMES: [usize; 8] = [11, 13, 17, 19, 23, 29, 31, 37];
+
+    let size = size.max(1);
+    let mut primes = PRIMES;
+    rng.shuffle(&mut primes);
+    let primes = &primes[..6];
+
+    // middle nodes never end in A or Z
+    let mut names = ALNUM.iter().flat_map(|&a| {
+        ALNUM.iter().flat_map(move |&b| {
+            ALNUM
+                .iter()
+                .filter(|c| !b"AZ".contains(c))
+                .map(move |&c| String::from_utf8(vec![a, b, c]).unwrap())
+        })
+    });
+
+    let instructions = (0..size)
+        .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+        .collect::<String>();
+
+    let mut lines = vec![];
+    for (g, prime) in primes.iter().enumerate() {
+        let (start, end) = if g == 0 {
+            ("AAA".to_owned(), "ZZZ".to_owned())
+        } else {
+            let prefix = char::from(b'0' + g as u8).to_string().repeat(2);
+            (format!("{prefix}A"), format!("{prefix}Z"))
+        };
+
+        let len = size * prime;
+        let mut ring = (1..len)
+            .map(|_| names.next().expect("size too large"))
+            .collect::<Vec<_>>();
+        ring.push(end);
+
+        lines.push(format!("{start} = ({0}, {0})", ring[0]));
+        for i in 0..len {
+            let next = &ring[(i + 1) % len];
+            lines.push(format!("{} = ({next}, {next})", ring[i]));
+        }
+    }
+    rng.shuffle(&mut lines);
+
+    format!("{instructions}\n\n{}\n", lines.join("\n"))
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
+
+#[cfg(test)]
+#[test]
+fn test_part2_offset_loops() {
+    // 11A loops through 11Z every 2 steps; 22A hits 22Z at 1, then every 3 steps. an LCM of
+    // the first hits would say 2, but 22A is at 22B then
+    let input = parse(
+        "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)",
+    );
+    assert_eq!(part2(&input), 4);
+}
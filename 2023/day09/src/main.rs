@@ -65,4 +65,19 @@ fn part2(n: &[In]) -> Out {
         .sum()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let degree = rng.index(5);
+        let coeffs = (0..=degree).map(|_| rng.range(-5..6)).collect::<Vec<_>>();
+        let row = (0..21)
+            .map(|x: i64| coeffs.iter().rev().fold(0, |acc, c| acc * x + c))
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        s += &row.join(" ");
+        s.push('\n');
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
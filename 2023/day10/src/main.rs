@@ -134,15 +134,18 @@ fn part2(n: &[In]) -> Out {
         .map(|row| row.into_iter().step_by(2).collect())
         .collect();
 
-    _print(&grid);
+    if trace::enabled(trace::Level::Debug) {
+        print(&grid);
+    }
 
     grid.iter().flatten().filter(|c| c == &&FILL).count()
 }
 
-fn _print(grid: &[In]) {
+fn print(grid: &[In]) {
     for row in grid {
-        for cell in row {
-            let ch = match *cell {
+        let row = row
+            .iter()
+            .map(|cell| match *cell {
                 '|' => '│',
                 '-' => '─',
                 'F' => '┌',
@@ -150,11 +153,47 @@ fn _print(grid: &[In]) {
                 'L' => '└',
                 'J' => '┘',
                 c => c,
-            };
-            print!("{ch}");
-        }
-        println!();
+            })
+            .collect::<String>();
+        debug!("{row}");
+    }
+}
+
+// the loop is the outline of a random blob grown from the middle cell, which part 2 relies on
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let cells = 2 * size.max(1) + 1;
+    let blob = gen::blob(rng, cells, cells);
+    let outline = gen::outline(&blob);
+
+    const JUNK: [char; 7] = ['.', '|', '-', 'L', 'J', '7', 'F'];
+    let mut grid = vec![vec!['.'; cells + 1]; cells + 1];
+    for row in &mut grid {
+        row.fill_with(|| *rng.choose(&JUNK));
+    }
+
+    for (i, &(pos, out)) in outline.iter().enumerate() {
+        let prev = outline[(i + outline.len() - 1) % outline.len()].1;
+        let back = (-prev.0, -prev.1);
+        let has = |d: (i64, i64)| out == d || back == d;
+        let (n, e, s, w) = (has((0, -1)), has((1, 0)), has((0, 1)), has((-1, 0)));
+        grid[pos.y as usize][pos.x as usize] = match (n, e, s, w) {
+            (true, _, true, _) => '|',
+            (_, true, _, true) => '-',
+            (true, true, _, _) => 'L',
+            (true, _, _, true) => 'J',
+            (_, _, true, true) => '7',
+            _ => 'F',
+        };
     }
+
+    // part 1 sets off southward from S
+    let starts = outline.iter().filter(|(_, dir)| *dir == (0, 1));
+    let starts = starts.map(|(pos, _)| *pos).collect::<Vec<_>>();
+    let start = rng.choose(&starts);
+    grid[start.y as usize][start.x as usize] = 'S';
+
+    let lines = grid.into_iter().map(String::from_iter);
+    lines.collect::<Vec<_>>().join("\n") + "\n"
 }
 
-util::register!(parse, part1, part2);
+util::register!(parse, part1, part2, @gen generate);
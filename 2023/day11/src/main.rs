@@ -65,4 +65,19 @@ fn common(rows: &[In], growth: N) -> Out {
     sum
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let empty_rows = (0..size).map(|_| rng.chance(0.1)).collect::<Vec<_>>();
+    let empty_cols = (0..size).map(|_| rng.chance(0.1)).collect::<Vec<_>>();
+
+    let mut s = String::new();
+    for &empty_row in &empty_rows {
+        for &empty_col in &empty_cols {
+            let galaxy = !empty_row && !empty_col && rng.chance(0.03);
+            s.push(if galaxy { '#' } else { '.' });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -123,7 +123,36 @@ fn part2(n: &[In]) -> Out {
         .sum()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..size {
+        let len = 5 + rng.index(16);
+        let mut springs = (0..len).map(|_| rng.chance(0.5)).collect::<Vec<_>>();
+        springs[rng.index(len)] = true;
+        let runs = rle(&springs)
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>();
+
+        // part 1 brute-forces every unknown, so keep those in check
+        let mut unknowns = 0;
+        for damaged in springs {
+            let c = if unknowns < 16 && rng.chance(0.4) {
+                unknowns += 1;
+                '?'
+            } else if damaged {
+                '#'
+            } else {
+                '.'
+            };
+            s.push(c);
+        }
+        s += &format!(" {}\n", runs.join(","));
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
 
 #[cfg(test)]
 #[test]
@@ -133,3 +162,26 @@ fn test_rle() {
     let runs = rle(&springs);
     assert_eq!(runs, vec![1, 3, 1, 6]);
 }
+
+#[cfg(test)]
+#[test]
+fn test_counts_differential() {
+    use util::diff::{self, Shrink};
+
+    diff::check(
+        500,
+        |rng| {
+            let len = 1 + rng.index(12);
+            let springs = (0..len).map(|_| rng.chance(0.5)).collect::<Vec<_>>();
+            let runs = rle(&springs);
+            let springs = springs
+                .into_iter()
+                .map(|s| (!rng.chance(0.5)).then_some(s))
+                .collect();
+            (springs, runs)
+        },
+        Shrink::shrink,
+        counts,
+        counts_opt,
+    );
+}
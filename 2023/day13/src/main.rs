@@ -0,0 +1,138 @@
+#![cfg_attr(test, feature(test))]
+
+type N = usize;
+type In = Vec<Vec<Vec<bool>>>;
+type Out = N;
+
+fn parse(s: &'static str) -> In {
+    Vec::from_iter(s.lines())
+        .split(|line| line.is_empty())
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| line.chars().map(|c| c == '#').collect())
+                .collect()
+        })
+        .collect()
+}
+
+fn find_reflection(group: &[Vec<bool>], nope: Option<N>) -> Option<N> {
+    for y in 1..group.len() {
+        if nope == Some(y * 100) {
+            continue;
+        }
+
+        if group[..y]
+            .iter()
+            .rev()
+            .zip(&group[y..])
+            .all(|(a, b)| a == b)
+        {
+            return Some(y * 100);
+        }
+    }
+
+    let w = group[0].len();
+
+    for x in 1..w {
+        if nope == Some(x) {
+            continue;
+        }
+
+        if (0..x)
+            .rev()
+            .zip(x..w)
+            .all(|(i, j)| group.iter().all(|r| r[i] == r[j]))
+        {
+            return Some(x);
+        }
+    }
+
+    None
+}
+
+fn part1(n: &In) -> Out {
+    n.iter().map(|x| find_reflection(x, None).unwrap()).sum()
+}
+
+fn find_alt_reflection(x: &[Vec<bool>]) -> Option<N> {
+    let mut group = x.to_owned();
+    let h = group.len();
+    let w = group[0].len();
+    for y in 0..h {
+        for x in 0..w {
+            let nope = find_reflection(&group, None).unwrap();
+            group[y][x] = !group[y][x];
+            if let Some(n) = find_reflection(&group, Some(nope)) {
+                return Some(n);
+            }
+            group[y][x] = !group[y][x];
+        }
+    }
+    None
+}
+
+fn part2(n: &In) -> Out {
+    n.iter().map(|x| find_alt_reflection(x).unwrap()).sum()
+}
+
+// Each pattern is mirrored across a column, and across a row except for one smudged cell
+// that the column mirror doesn't reach.
+fn generate(rng: &mut util::rng::Rng, size: usize) -> String {
+    let patterns = (0..size).map(|_| {
+        let w = 5 + rng.index(12);
+        let h = 5 + rng.index(12);
+
+        let (x1, reach_x) = loop {
+            let x1 = 1 + rng.index(w - 1);
+            let reach = x1.min(w - x1);
+            if 2 * reach < w {
+                break (x1, reach);
+            }
+        };
+        let y2 = 1 + rng.index(h - 1);
+        let reach_y = y2.min(h - y2);
+
+        // cells tied together by either mirror must match
+        let mut parent = (0..w * h).collect::<Vec<_>>();
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            if parent[i] != i {
+                parent[i] = find(parent, parent[i]);
+            }
+            parent[i]
+        }
+        let mut union = |a: usize, b: usize| {
+            let (a, b) = (find(&mut parent, a), find(&mut parent, b));
+            parent[a] = b;
+        };
+        for y in 0..h {
+            for x in x1 - reach_x..x1 {
+                union(y * w + x, y * w + 2 * x1 - 1 - x);
+            }
+        }
+        for y in y2 - reach_y..y2 {
+            for x in 0..w {
+                union(y * w + x, (2 * y2 - 1 - y) * w + x);
+            }
+        }
+
+        let colors = (0..w * h).map(|_| rng.chance(0.5)).collect::<Vec<_>>();
+        let mut cells = (0..w * h)
+            .map(|i| colors[find(&mut parent, i)])
+            .collect::<Vec<_>>();
+
+        let outside = (0..w).filter(|x| !(x1 - reach_x..x1 + reach_x).contains(x));
+        let x = *rng.choose(&outside.collect::<Vec<_>>());
+        let y = y2 - reach_y + rng.index(2 * reach_y);
+        cells[y * w + x] = !cells[y * w + x];
+
+        let rows = cells.chunks(w).map(|row| {
+            let row = row.iter().map(|c| if *c { '#' } else { '.' });
+            row.collect::<String>() + "\n"
+        });
+        rows.collect::<String>()
+    });
+    patterns.collect::<Vec<_>>().join("\n")
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
@@ -120,4 +120,12 @@ fn part2(n: &[In]) -> Out {
     load(&grid)
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    gen::grid(rng, size, size, |rng| match rng.index(10) {
+        0 | 1 => 'O',
+        2 => '#',
+        _ => '.',
+    })
+}
+
+util::register!(parse, part1, part2, @gen generate);
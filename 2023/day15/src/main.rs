@@ -43,4 +43,25 @@ fn part2(n: &In) -> u32 {
     s
 }
 
-util::register!(parse, part1, part2, @alt);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let labels = (0..size / 4 + 1)
+        .map(|_| {
+            let len = 2 + rng.index(5);
+            (0..len)
+                .map(|_| char::from(b'a' + rng.index(26) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    let steps = (0..size.max(1)).map(|_| {
+        let label = rng.choose(&labels);
+        if rng.chance(0.3) {
+            format!("{label}-")
+        } else {
+            format!("{label}={}", 1 + rng.index(9))
+        }
+    });
+    steps.collect::<Vec<_>>().join(",") + "\n"
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
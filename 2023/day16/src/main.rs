@@ -134,4 +134,15 @@ fn part2(n: &[In]) -> Out {
         .unwrap()
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    const SPACES: [char; 4] = ['|', '-', '/', '\\'];
+    gen::grid(rng, size, size, |rng| {
+        if rng.chance(0.85) {
+            '.'
+        } else {
+            *rng.choose(&SPACES)
+        }
+    })
+}
+
+util::register!(parse, part1, part2, @gen generate);
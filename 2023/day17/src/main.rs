@@ -68,6 +68,7 @@ fn solve(n: &[In], f: impl Fn(Crucible) -> Vec<Crucible>) -> Out {
     distances.insert(current, 0);
     unvisited.insert(current);
 
+    let mut progress = progress::Progress::new("visited");
     loop {
         for neighbor in f(current) {
             if !valid(neighbor.pos) || visited.contains(&neighbor) {
@@ -81,11 +82,7 @@ fn solve(n: &[In], f: impl Fn(Crucible) -> Vec<Crucible>) -> Out {
         visited.insert(current);
         unvisited.remove(&current);
 
-        /*
-        if visited.len() % 1000 == 0 {
-            println!("{}", visited.len());
-        }
-        */
+        progress.set(visited.len() as u64);
 
         if unvisited.is_empty() {
             break;
@@ -131,4 +128,10 @@ fn part2(n: &[In]) -> Out {
     })
 }
 
-util::register!(parse, part1, part2);
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    // part 2 needs room for four steps before each turn
+    let size = size.max(5);
+    gen::grid(rng, size, size, |rng| char::from(b'1' + rng.index(9) as u8))
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -114,4 +114,54 @@ fn part2(n: &[In]) -> Out {
     })
 }
 
-util::register!(parse, part1, part2);
+// Both plans trace the clockwise outline of a random blob; part 2's is scaled way up.
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let scale = 2 + rng.range(0..4);
+    let mut plan = |scale: i64| {
+        let blob = gen::blob(rng, size.max(1), size.max(1));
+        let mut runs: Vec<((i64, i64), i64)> = vec![];
+        for (_, dir) in gen::outline(&blob) {
+            match runs.last_mut() {
+                Some((d, n)) if *d == dir => *n += scale,
+                _ => runs.push((dir, scale)),
+            }
+        }
+        runs
+    };
+    let mut plan1 = plan(scale);
+    let mut plan2 = plan(1000);
+
+    // both plans share lines, so split runs of the shorter one until the counts match
+    while plan1.len() != plan2.len() {
+        let short = if plan1.len() < plan2.len() {
+            &mut plan1
+        } else {
+            &mut plan2
+        };
+        let i = (0..short.len()).max_by_key(|i| short[*i].1).unwrap();
+        let (dir, n) = short[i];
+        short[i].1 = n / 2;
+        short.insert(i + 1, (dir, n - n / 2));
+    }
+
+    let mut s = String::new();
+    for ((dir1, n1), (dir2, n2)) in plan1.into_iter().zip(plan2) {
+        let letter = match dir1 {
+            (1, 0) => 'R',
+            (0, 1) => 'D',
+            (-1, 0) => 'L',
+            _ => 'U',
+        };
+        let digit = match dir2 {
+            (1, 0) => 0,
+            (0, 1) => 1,
+            (-1, 0) => 2,
+            _ => 3,
+        };
+        assert!(n2 < 0x100000);
+        s += &format!("{letter} {n1} (#{n2:05x}{digit})\n");
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
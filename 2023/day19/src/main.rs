@@ -203,4 +203,68 @@ fn part2(n: &In) -> Out {
     states["A"].iter().map(|x| x.len()).sum()
 }
 
-util::register!(parse, part1, part2, @alt);
+// workflows form a tree rooted at `in`, with at most `size` workflows and `size` parts. part 2
+// assumes every threshold splits the ranges reaching its rule, so each workflow remembers them.
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut taken = HashSet::from(["in".to_owned()]);
+    let mut queue = std::collections::VecDeque::from([("in".to_owned(), [(1, 4001); 4])]);
+    let mut count = 1;
+
+    let mut flows = vec![];
+    while let Some((name, mut ranges)) = queue.pop_front() {
+        let mut target = |rng: &mut rng::Rng, ranges: [(N, N); 4]| {
+            if count < size && rng.chance(0.6) {
+                let name = loop {
+                    let len = 2 + rng.index(2);
+                    let name = (0..len)
+                        .map(|_| char::from(b'a' + rng.index(26) as u8))
+                        .collect::<String>();
+                    if taken.insert(name.clone()) {
+                        break name;
+                    }
+                };
+                count += 1;
+                queue.push_back((name.clone(), ranges));
+                name
+            } else if rng.chance(0.5) {
+                "A".to_owned()
+            } else {
+                "R".to_owned()
+            }
+        };
+
+        let mut rules = vec![];
+        for _ in 0..1 + rng.index(3) {
+            let i = rng.index(4);
+            let (lo, hi) = ranges[i];
+            if hi - lo < 3 {
+                continue;
+            }
+            let mut truth = ranges;
+            let (op, n) = if rng.chance(0.5) {
+                let n = lo + 1 + rng.below(hi - lo - 1);
+                (truth[i], ranges[i]) = ((lo, n), (n, hi));
+                ('<', n)
+            } else {
+                let n = lo + rng.below(hi - lo - 1);
+                (truth[i], ranges[i]) = ((n + 1, hi), (lo, n + 1));
+                ('>', n)
+            };
+            let field = ["x", "m", "a", "s"][i];
+            rules.push(format!("{field}{op}{n}:{}", target(rng, truth)));
+        }
+        rules.push(target(rng, ranges));
+
+        flows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+
+    let parts = (0..size.max(1)).map(|_| {
+        let [x, m, a, s] = [(); 4].map(|_| 1 + rng.below(4000));
+        format!("{{x={x},m={m},a={a},s={s}}}")
+    });
+    let parts = parts.collect::<Vec<_>>();
+
+    format!("{}\n\n{}\n", flows.join("\n"), parts.join("\n"))
+}
+
+util::register!(parse, part1, part2, @alt, @gen generate);
@@ -164,7 +164,8 @@ fn part2(n: &[In]) -> Out {
 
     let mut pulses = VecDeque::new();
     let mut i = 0;
-    let mut periods = HashMap::new();
+    // the first two presses on which each input to the final junction goes high
+    let mut highs = HashMap::<&str, Vec<i64>>::new();
 
     loop {
         pulses.push_back(("button", false, "broadcaster"));
@@ -174,10 +175,15 @@ fn part2(n: &[In]) -> Out {
             let (src_module, inbound_value, cur_module) = pulse;
 
             if cur_module == final_junction && inbound_value {
-                // println!("[{i}] {src_module} -{inbound_value}-> {cur_module}");
-                periods.insert(src_module, i);
-                if periods.len() == goal {
-                    return periods.into_values().reduce(num_integer::lcm).unwrap();
+                debug!("[{i}] {src_module} -{inbound_value}-> {cur_module}");
+                let seen = highs.entry(src_module).or_default();
+                if seen.last() != Some(&i) && seen.len() < 2 {
+                    seen.push(i);
+                }
+                if highs.len() == goal && highs.values().all(|v| v.len() == 2) {
+                    let cycles = highs.values().map(|v| (v[0], v[1] - v[0]));
+                    let cycles = cycles.collect::<Vec<_>>();
+                    return math::first_common_time(&cycles).expect("rx never goes low") as Out;
                 }
             }
 
@@ -194,4 +200,40 @@ fn part2(n: &[In]) -> Out {
     }
 }
 
-util::register!(parse, part1, part2);
+// `size` 12-bit counters like the real input's, each reporting to `rx` through an inverter.
+// their periods multiply, so more than 5 would overflow the answer
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut lines = vec![];
+    let mut starts = vec![];
+
+    for c in 0..size.clamp(1, 5) {
+        let ffs = (0..12).map(|i| format!("f{c}b{i}")).collect::<Vec<_>>();
+        let hub = format!("h{c}");
+        let period = 2049 + 2 * rng.below(1024);
+
+        // the hub listens to the set bits and resets the clear ones
+        let mut hub_outputs = vec![ffs[0].clone(), format!("i{c}")];
+        for (i, ff) in ffs.iter().enumerate() {
+            let mut outputs = vec![];
+            if let Some(next) = ffs.get(i + 1) {
+                outputs.push(next.clone());
+            }
+            if period >> i & 1 == 1 {
+                outputs.push(hub.clone());
+            } else {
+                hub_outputs.push(ff.clone());
+            }
+            lines.push(format!("%{ff} -> {}", outputs.join(", ")));
+        }
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&i{c} -> out"));
+        starts.push(ffs[0].clone());
+    }
+
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines.push("&out -> rx".to_owned());
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -240,4 +240,36 @@ fn part2(n: &[In]) -> Out {
     total
 }
 
-util::register!(parse, part1, part2);
+// `size` bricks of up to four cubes, scattered over a 10x10 footprint
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let mut occupied = BTreeSet::new();
+    let mut s = String::new();
+    let mut placed = 0;
+    while placed < size {
+        let len = rng.range(0..4) as N;
+        let a = Xyz {
+            x: rng.range(0..10) as N,
+            y: rng.range(0..10) as N,
+            z: rng.range(1..3 * size as i64 + 2) as N,
+        };
+        let b = match rng.index(3) {
+            0 => a + Xyz { x: len, y: 0, z: 0 },
+            1 => a + Xyz { x: 0, y: len, z: 0 },
+            _ => a + Xyz { x: 0, y: 0, z: len },
+        };
+        if b.x > 9 || b.y > 9 {
+            continue;
+        }
+
+        let brick = Brick { a, b };
+        if brick.iter().any(|v| occupied.contains(&v)) {
+            continue;
+        }
+        occupied.extend(brick.iter());
+        s += &format!("{},{},{}~{},{},{}\n", a.x, a.y, a.z, b.x, b.y, b.z);
+        placed += 1;
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -1,36 +1,12 @@
-#![cfg_attr(test, feature(test))]
+//! Cell-by-cell hike search, kept as a reference for the compressed graph in `main.rs`.
 
 use std::collections::VecDeque;
 
-use owo_colors::OwoColorize;
 use util::*;
 
-type N = i32;
-type P = Pos<N>;
+use super::{In, Out, Tile, N, P};
 
-type In = Vec<Tile>;
-type Out = usize;
-
-#[derive(PartialEq, Copy, Clone)]
-enum Tile {
-    Path,
-    Forest,
-    Slope((N, N)),
-}
-
-fn parse(s: &'static str) -> In {
-    s.chars()
-        .map(|c| match c {
-            '.' => Tile::Path,
-            '#' => Tile::Forest,
-            'v' => Tile::Slope((0, 1)),
-            '>' => Tile::Slope((1, 0)),
-            _ => unreachable!(),
-        })
-        .collect()
-}
-
-fn part1(n: &[In]) -> Out {
+pub fn part1(n: &[In]) -> Out {
     let mut hikes = vec![vec![P::new(1, 0)]];
     let w = n[0].len() as N;
     let h = n.len() as N;
@@ -109,13 +85,13 @@ impl Hike {
 
         let mut marked = vec![poi];
 
-        // let mut num = 0;
+        let mut num = 0;
 
         while !marked.is_empty() {
             for point in std::mem::take(&mut marked) {
                 if self.is_dead_end(n, point) {
                     self.dead_ends.insert(point);
-                    // num += 1;
+                    num += 1;
                     for d in NESW {
                         if self.can_visit(point + d, n) {
                             marked.push(point + d);
@@ -125,9 +101,9 @@ impl Hike {
             }
         }
 
-        // if num > 0 {
-        // println!("{num} dead ends marked");
-        // }
+        if num > 0 {
+            trace!("{num} dead ends marked");
+        }
     }
 
     fn is_dead_end(&self, n: &[In], p: P) -> bool {
@@ -150,69 +126,13 @@ impl Hike {
     }
 }
 
-fn print_i(i: usize) {
-    let s = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-    let c = s[i] as char;
-    let s = c.color(owo_colors::XtermColors::from(i as u8 + 1));
-    print!("{s}");
-}
-
-fn part2(n: &[In]) -> Out {
+pub fn part2(n: &[In]) -> Out {
     let mut start = Hike::default();
     start.go(P::new(1, 0));
     let mut hikes = VecDeque::from([start]);
     let w = n[0].len() as N;
     let h = n.len() as N;
 
-    if true {
-        let mut foo = BTreeMap::new();
-        let mut bar = Vec::new();
-
-        for y in 0..h {
-            for x in 0..w {
-                let p = P { x, y };
-                let t = n[p.y as usize][p.x as usize];
-
-                if t != Tile::Forest {
-                    print!(" ");
-                    continue;
-                }
-
-                if let Some(&i) = foo.get(&p) {
-                    print_i(i);
-                    continue;
-                }
-
-                // new group
-                let group_id = bar.len();
-                let group = {
-                    bar.push(BTreeSet::new());
-                    &mut bar[group_id]
-                };
-
-                let mut unvisited = BTreeSet::from([p]);
-                while let Some(q) = unvisited.pop_last() {
-                    group.insert(q);
-                    assert_eq!(foo.insert(q, group_id), None);
-                    for d in NESW {
-                        let r = q + d;
-                        if (0..h).contains(&r.y)
-                            && (0..w).contains(&r.x)
-                            && !group.contains(&r)
-                            && n[r.y as usize][r.x as usize] == Tile::Forest
-                        {
-                            unvisited.insert(r);
-                        }
-                    }
-                }
-                print_i(group_id);
-            }
-            println!();
-        }
-
-        return 0;
-    }
-
     let mut finished_hikes: BTreeSet<Hike> = Default::default();
 
     let goal = P::new(w - 2, h - 1);
@@ -229,7 +149,7 @@ fn part2(n: &[In]) -> Out {
 
         if num_options == 0 {
             if hike.current == goal {
-                println!("{} {}", hikes.len(), finished_hikes.len());
+                debug!("{} {}", hikes.len(), finished_hikes.len());
                 finished_hikes.insert(hike);
             }
             continue;
@@ -260,5 +180,3 @@ fn part2(n: &[In]) -> Out {
         .max()
         .unwrap()
 }
-
-util::register!(parse, part1, part2);
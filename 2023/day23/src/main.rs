@@ -3,6 +3,9 @@
 use itertools::Itertools;
 use util::*;
 
+#[cfg(test)]
+mod graph;
+
 type N = i32;
 type P = Pos<N>;
 
@@ -162,8 +165,10 @@ fn solve(map: &[In], part2: bool) -> Out {
     let mut finished_hikes = vec![];
     let mut ongoing_hikes = vec![Hike::new(start)];
 
+    let mut progress = progress::Progress::new("hikes explored");
     while !ongoing_hikes.is_empty() {
         for hike in std::mem::take(&mut ongoing_hikes) {
+            progress.inc();
             if hike.current == goal {
                 finished_hikes.push(hike);
                 continue;
@@ -191,4 +196,124 @@ fn part2(n: &[In]) -> Out {
     solve(n, true)
 }
 
-util::register!(parse, part1, part2);
+/// A lattice of `k`×`k` junctions joined by sloped corridors, shaped like the real input.
+#[derive(Debug, Clone)]
+struct Maze {
+    k: usize,
+    right: Vec<bool>,
+    down: Vec<bool>,
+}
+
+impl Maze {
+    const SPACING: usize = 4;
+
+    /// Each junction gets one way out, then the other with some chance, so the maze is valid
+    /// without retrying.
+    fn random(rng: &mut rng::Rng, k: usize) -> Self {
+        let mut maze = Maze {
+            k,
+            right: vec![false; k * k],
+            down: vec![false; k * k],
+        };
+        for i in 0..k * k - 1 {
+            let (can_right, can_down) = (i % k + 1 < k, i / k + 1 < k);
+            let exit_right = !can_down || (can_right && rng.chance(0.5));
+            maze.right[i] = exit_right || (can_right && rng.chance(0.4));
+            maze.down[i] = !exit_right || (can_down && rng.chance(0.4));
+        }
+        debug_assert!(maze.is_valid());
+        maze
+    }
+
+    /// Every junction but the last needs a way out, or the directed hike gets stuck.
+    fn is_valid(&self) -> bool {
+        (0..self.k * self.k - 1).all(|i| self.right[i] || self.down[i])
+    }
+
+    #[cfg(test)]
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = vec![];
+        for i in 0..self.k * self.k {
+            if self.right[i] {
+                let mut m = self.clone();
+                m.right[i] = false;
+                out.push(m);
+            }
+            if self.down[i] {
+                let mut m = self.clone();
+                m.down[i] = false;
+                out.push(m);
+            }
+        }
+        out.retain(Maze::is_valid);
+        out
+    }
+
+    fn render(&self) -> String {
+        let s = Self::SPACING;
+        let size = s * (self.k - 1) + 3;
+        let mut grid = vec![vec![b'#'; size]; size];
+        grid[0][1] = b'.';
+        grid[size - 1][size - 2] = b'.';
+
+        for i in 0..self.k * self.k {
+            let (x, y) = (1 + s * (i % self.k), 1 + s * (i / self.k));
+            grid[y][x] = b'.';
+            if self.right[i] {
+                grid[y][x + 1..x + s].fill(b'.');
+                (grid[y][x + 1], grid[y][x + s - 1]) = (b'>', b'>');
+            }
+            if self.down[i] {
+                for row in &mut grid[y + 1..y + s] {
+                    row[x] = b'.';
+                }
+                (grid[y + 1][x], grid[y + s - 1][x]) = (b'v', b'v');
+            }
+        }
+
+        let lines = grid.into_iter().map(|row| String::from_utf8(row).unwrap());
+        lines.collect::<Vec<_>>().join("\n")
+    }
+
+    #[cfg(test)]
+    fn parse(&self) -> Vec<In> {
+        parse_input_lines(self.render().leak(), parse)
+    }
+}
+
+// `size` is the number of junctions along each side
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    Maze::random(rng, size.max(2)).render() + "\n"
+}
+
+util::register!(parse, part1, part2, @gen generate);
+
+#[cfg(test)]
+#[test]
+fn test_part1_differential() {
+    diff::check(
+        200,
+        |rng| {
+            let k = 2 + rng.index(3);
+            Maze::random(rng, k)
+        },
+        Maze::shrink,
+        |maze| graph::part1(&maze.parse()),
+        |maze| part1(&maze.parse()),
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_part2_differential() {
+    diff::check(
+        50,
+        |rng| {
+            let k = 2 + rng.index(3);
+            Maze::random(rng, k)
+        },
+        Maze::shrink,
+        |maze| graph::part2(&maze.parse()),
+        |maze| part2(&maze.parse()),
+    );
+}
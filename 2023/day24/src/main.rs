@@ -154,8 +154,8 @@ fn part2_mini(input: &[In; 3]) -> Option<(f64, f64, f64)> {
 
     // the rounding is a bit of a crapshoot
     row_reduce(&mut equations)?;
-    back_substitute(&mut equations);
-    reduce_row_echelon(&mut equations);
+    back_substitute(&mut equations)?;
+    reduce_row_echelon(&mut equations)?;
     round(&mut equations);
 
     let (_qxy, _qyz, _qxz, px, py, pz, _vx, _vy, _vz) = equations
@@ -167,7 +167,8 @@ fn part2_mini(input: &[In; 3]) -> Option<(f64, f64, f64)> {
     Some((px, py, pz))
 }
 
-fn part2(n: &[In]) -> Out {
+// The rock's coordinate sum, from the first trio of hailstones whose solution rounds cleanly.
+fn solve(n: &[In]) -> Option<Out> {
     // alternative approach: try the cartesian product of ceil vs. floor for each coordinate
     // it's only 8 combinations - check if that choice of rounding is actually a solution
 
@@ -177,14 +178,20 @@ fn part2(n: &[In]) -> Out {
             continue;
         };
         // check that rounding errors were small enough to get smoothed out
-        if px.fract() == 0.0 && py.fract() == 0.0 && pz.fract() == 0.0 {
-            // for some reason my answer comes out negative, but that's not really a problem
-            assert_eq!(px.signum(), py.signum());
-            assert_eq!(py.signum(), pz.signum());
-            return (px as N + py as N + pz as N).abs();
+        if px.fract() != 0.0 || py.fract() != 0.0 || pz.fract() != 0.0 {
+            continue;
+        }
+        // for some reason my answer comes out negative, but that's not really a problem
+        // mixed signs mean the rounding went wrong after all
+        if px.signum() == py.signum() && py.signum() == pz.signum() {
+            return Some((px as N + py as N + pz as N).abs());
         }
     }
-    panic!("well darn");
+    None
+}
+
+fn part2(n: &[In]) -> Out {
+    solve(n).expect("well darn")
 }
 
 // Get a matrix into triangular form.
@@ -227,10 +234,13 @@ fn row_reduce<const N: usize>(matrix: &mut [[f64; N]]) -> Option<()> {
 }
 
 // Get a triangular matrix into row-echelon form.
-fn back_substitute<const N: usize>(matrix: &mut [[f64; N]]) {
+fn back_substitute<const N: usize>(matrix: &mut [[f64; N]]) -> Option<()> {
     for i in (0..matrix.len()).rev() {
         let src = matrix[i];
-        assert_ne!(src[i], 0.0);
+        if src[i] == 0.0 {
+            // rounding cancelled out a diagonal value
+            return None;
+        }
 
         for j in 0..i {
             let dst = &mut matrix[j];
@@ -243,17 +253,23 @@ fn back_substitute<const N: usize>(matrix: &mut [[f64; N]]) {
             }
         }
     }
+
+    Some(())
 }
 
 // Get a row-echelon matrix into reduced row-echelon form.
-fn reduce_row_echelon<const N: usize>(matrix: &mut [[f64; N]]) {
+fn reduce_row_echelon<const N: usize>(matrix: &mut [[f64; N]]) -> Option<()> {
     for i in 0..matrix.len() {
         let a = matrix[i][i];
-        assert_ne!(a, 0.0);
+        if a == 0.0 {
+            return None;
+        }
         for b in &mut matrix[i] {
             *b /= a;
         }
     }
+
+    Some(())
 }
 
 // Smooth out floating-point rounding errors.
@@ -268,7 +284,70 @@ fn round<const N: usize>(matrix: &mut [[f64; N]]) {
     }
 }
 
-util::register!(parse, part1, part2);
+// a rock and hailstones that it hits at distinct times, at the scale of the real input
+fn hailstorm(rng: &mut rng::Rng, size: usize) -> (Xyz, Vec<In>) {
+    let rock = Xyz {
+        x: rng.range(200_000_000_000_000..400_000_000_000_000),
+        y: rng.range(200_000_000_000_000..400_000_000_000_000),
+        z: rng.range(200_000_000_000_000..400_000_000_000_000),
+    };
+    let rock_vel = Xyz {
+        x: rng.range(-300..300),
+        y: rng.range(-300..300),
+        z: rng.range(-300..300),
+    };
+
+    let mut times = HashSet::new();
+    let mut hail = vec![];
+    while hail.len() < size.max(3) {
+        let t = rng.range(100_000_000_000..1_000_000_000_000);
+        let vel = Xyz {
+            x: rng.range(-300..300),
+            y: rng.range(-300..300),
+            z: rng.range(-300..300),
+        };
+        if vel.x == 0 || vel.x == rock_vel.x || times.contains(&t) {
+            continue;
+        }
+        let pos = Xyz {
+            x: rock.x + t * (rock_vel.x - vel.x),
+            y: rock.y + t * (rock_vel.y - vel.y),
+            z: rock.z + t * (rock_vel.z - vel.z),
+        };
+        // part 2 works in floats, so stay in the real input's range
+        let range = 100_000_000_000_000..600_000_000_000_000;
+        if ![pos.x, pos.y, pos.z].iter().all(|c| range.contains(c)) {
+            continue;
+        }
+        times.insert(t);
+        hail.push(In { pos, vel });
+    }
+    (rock, hail)
+}
+
+// part 2 only rounds its way to the rock, so re-roll until it finds the one that was chosen
+fn rock_and_hail(rng: &mut rng::Rng, size: usize) -> (Xyz, Vec<In>) {
+    loop {
+        let (rock, hail) = hailstorm(rng, size);
+        if solve(&hail) == Some(rock.x + rock.y + rock.z) {
+            return (rock, hail);
+        }
+    }
+}
+
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let (_, hail) = rock_and_hail(rng, size);
+    let mut s = String::new();
+    for In { pos, vel } in hail {
+        s += &format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            pos.x, pos.y, pos.z, vel.x, vel.y, vel.z
+        );
+    }
+    s
+}
+
+util::register!(parse, part1, part2, @gen generate);
 
 #[cfg(test)]
 mod tests {
@@ -333,6 +412,23 @@ mod tests {
         let expected = SOLVED;
         check_eq(matrix, expected);
     }
+
+    #[test]
+    fn test_generate() {
+        for size in [2, 3, 10, 50, 300] {
+            for seed in 0..8 {
+                let input = generate(&mut rng::Rng::new(seed), size);
+                let input = Box::leak(input.into_boxed_str());
+                let hail = input.lines().map(parse).collect_vec();
+                let (rock, _) = rock_and_hail(&mut rng::Rng::new(seed), size);
+                assert_eq!(
+                    part2(&hail),
+                    rock.x + rock.y + rock.z,
+                    "size {size}, seed {seed}"
+                );
+            }
+        }
+    }
 }
 
 pub fn print_matrix<const N: usize>(matrix: &[[f64; N]]) {
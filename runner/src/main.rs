@@ -0,0 +1,569 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use util::gen::{GENERATE_VAR, INPUT_VAR, NO_GENERATOR};
+use util::progress::{TIMED_OUT, TIMEOUT_VAR};
+use util::report::JSON_VAR;
+
+mod bench;
+
+/// Runs the day binaries against their real inputs and summarizes the results.
+#[derive(Parser)]
+struct Args {
+    /// Days to run (defaults to every day of the year)
+    days: Vec<u8>,
+
+    /// Year to run (defaults to the latest year in the workspace)
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Run days concurrently on a thread pool
+    #[arg(short, long)]
+    parallel: bool,
+
+    /// Number of worker threads in parallel mode (defaults to the number of CPUs)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Per-day timeout in seconds
+    #[arg(short, long, default_value_t = 300)]
+    timeout: u64,
+
+    /// Per-part timeout in seconds, enforced by the day binary itself so that part 2 still
+    /// runs if part 1 is stopped (when part 1 reports progress)
+    #[arg(long)]
+    part_timeout: Option<f64>,
+
+    /// Use the existing release binaries instead of running `cargo build` first
+    #[arg(long)]
+    no_build: bool,
+
+    /// Instead of the real inputs, time each day on generated inputs of these sizes
+    #[arg(long, value_delimiter = ',')]
+    scale: Vec<usize>,
+
+    /// Seed for generated inputs
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Print one JSON object per part instead of a table
+    #[arg(long, env = JSON_VAR)]
+    json: bool,
+
+    /// Time parsing and each part, and compare against the baseline in `bench_output.txt`
+    /// (which is created if missing)
+    #[arg(long)]
+    bench: bool,
+
+    /// Runs per day in bench mode; the median is kept
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+
+    /// Percentage change in bench mode that counts as a regression or improvement
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Overwrite the bench baseline with this run's results
+    #[arg(long)]
+    save_baseline: bool,
+}
+
+/// A `YYYY/dayNN` crate.
+struct Day {
+    year: u16,
+    num: u8,
+    dir: PathBuf,
+}
+
+impl Day {
+    /// The package and binary name, e.g. `y2023-day05`.
+    fn name(&self) -> String {
+        format!("y{}-day{:02}", self.year, self.num)
+    }
+
+    /// How the day is shown, e.g. `2023/day05`.
+    fn label(&self) -> String {
+        format!("{}/day{:02}", self.year, self.num)
+    }
+}
+
+enum Status {
+    Ok(Vec<String>),
+    Panicked(String),
+    TimedOut,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Verdict {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PartStatus {
+    Ok,
+    Panicked,
+    TimedOut,
+}
+
+/// One line of a day binary's JSON output (see `util::report`), plus the runner's verdict.
+#[derive(Serialize, Deserialize)]
+struct Part {
+    year: Option<u16>,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    duration_ms: f64,
+    status: PartStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(default)]
+    verdict: Option<Verdict>,
+}
+
+struct Outcome {
+    day: String,
+    status: Status,
+    elapsed: Duration,
+    verdict: Verdict,
+    parts: Vec<Part>,
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_owned()
+}
+
+fn numbered_dirs<T: std::str::FromStr>(dir: &Path, prefix: &str) -> Result<Vec<(T, PathBuf)>> {
+    let mut found = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let num = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.strip_prefix(prefix))
+            .and_then(|s| s.parse().ok());
+
+        if let Some(num) = num {
+            if path.is_dir() {
+                found.push((num, path));
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn find_days(root: &Path) -> Result<Vec<Day>> {
+    let mut days = vec![];
+    for (year, year_dir) in numbered_dirs(root, "")? {
+        for (num, dir) in numbered_dirs(&year_dir, "day")? {
+            if dir.join("Cargo.toml").exists() {
+                days.push(Day { year, num, dir });
+            }
+        }
+    }
+    days.sort_by_key(|d| (d.year, d.num));
+    Ok(days)
+}
+
+fn build(root: &Path, days: &[Day]) -> Result<()> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let mut cmd = Command::new(cargo);
+    cmd.current_dir(root)
+        .args(["build", "--release", "--quiet"]);
+    for day in days {
+        cmd.args(["-p", &day.name()]);
+    }
+    if !cmd.status().context("could not run cargo")?.success() {
+        bail!("build failed");
+    }
+    Ok(())
+}
+
+// drained on a helper thread so a chatty day can't stall on a full pipe
+fn drain(mut pipe: impl Read + Send + 'static) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = pipe.read_to_string(&mut buf);
+        buf
+    })
+}
+
+struct Run {
+    /// `None` if the process was killed for taking too long.
+    exit: Option<ExitStatus>,
+    stdout: String,
+    stderr: String,
+    elapsed: Duration,
+}
+
+impl Run {
+    fn status(&self) -> Status {
+        match self.exit {
+            None => Status::TimedOut,
+            Some(exit) if exit.success() => {
+                Status::Ok(self.stdout.lines().map(str::to_owned).collect())
+            }
+            Some(exit) if exit.code() == Some(TIMED_OUT) => Status::TimedOut,
+            Some(exit) => {
+                Status::Panicked(self.panic_message().unwrap_or_else(|| exit.to_string()))
+            }
+        }
+    }
+
+    fn panic_message(&self) -> Option<String> {
+        self.stderr
+            .lines()
+            .skip_while(|l| !l.contains("panicked at"))
+            .nth(1)
+            .map(str::to_owned)
+    }
+}
+
+fn run_exe(exe: &Path, envs: &[(&str, String)], timeout: Duration) -> Result<Run> {
+    let start = Instant::now();
+    let mut child = Command::new(exe)
+        .envs(envs.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("could not start {}", exe.display()))?;
+
+    let stdout = drain(child.stdout.take().unwrap());
+    let stderr = drain(child.stderr.take().unwrap());
+
+    let exit = loop {
+        if let Some(exit) = child.try_wait()? {
+            break Some(exit);
+        }
+        if start.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    };
+
+    Ok(Run {
+        exit,
+        elapsed: start.elapsed(),
+        stdout: stdout.join().unwrap(),
+        stderr: stderr.join().unwrap(),
+    })
+}
+
+fn run_day(
+    root: &Path,
+    day: &Day,
+    timeout: Duration,
+    part_timeout: Option<f64>,
+) -> Result<Outcome> {
+    let exe = root.join("target/release").join(day.name());
+    let mut envs = vec![(JSON_VAR, "1".to_owned())];
+    if let Some(secs) = part_timeout {
+        envs.push((TIMEOUT_VAR, secs.to_string()));
+    }
+    let run = run_exe(&exe, &envs, timeout)?;
+
+    let mut parts = run
+        .stdout
+        .lines()
+        .map(serde_json::from_str::<Part>)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("{} printed something other than JSON lines", day.name()))?;
+
+    // parts that never reported were cut off by the timeout, or by a panic outside any part
+    let mut leftover = run.elapsed.as_secs_f64() * 1000.0;
+    leftover -= parts.iter().map(|p| p.duration_ms).sum::<f64>();
+    leftover = (leftover * 1000.0).round() / 1000.0;
+    for part in parts.len() as u8 + 1..=2 {
+        let (status, message) = match run.exit {
+            None => (PartStatus::TimedOut, None),
+            Some(exit) if exit.code() == Some(TIMED_OUT) => (PartStatus::TimedOut, None),
+            Some(exit) => (
+                PartStatus::Panicked,
+                Some(run.panic_message().unwrap_or_else(|| exit.to_string())),
+            ),
+        };
+        parts.push(Part {
+            year: Some(day.year),
+            day: day.num,
+            part,
+            answer: None,
+            duration_ms: std::mem::take(&mut leftover),
+            status,
+            message,
+            verdict: None,
+        });
+    }
+
+    let status = if parts.iter().any(|p| p.status == PartStatus::TimedOut) {
+        Status::TimedOut
+    } else if let Some(p) = parts.iter().find(|p| p.status == PartStatus::Panicked) {
+        Status::Panicked(p.message.clone().unwrap_or_default())
+    } else {
+        Status::Ok(parts.iter().flat_map(|p| p.answer.clone()).collect())
+    };
+
+    let expected = expected(day);
+    for part in &mut parts {
+        let answer = part.answer.as_deref().map(str::trim);
+        part.verdict = Some(match &expected {
+            None => Verdict::Unknown,
+            Some(lines) if answer == lines.get(part.part as usize - 1).map(String::as_str) => {
+                Verdict::Pass
+            }
+            Some(_) => Verdict::Fail,
+        });
+    }
+    let verdict = match expected {
+        None => Verdict::Unknown,
+        Some(_) if parts.iter().all(|p| p.verdict == Some(Verdict::Pass)) => Verdict::Pass,
+        Some(_) => Verdict::Fail,
+    };
+
+    Ok(Outcome {
+        day: day.label(),
+        status,
+        elapsed: run.elapsed,
+        verdict,
+        parts,
+    })
+}
+
+/// The answers in `input.out.txt`, which uses the same two-line format as `test.out.txt`.
+fn expected(day: &Day) -> Option<Vec<String>> {
+    let expected = std::fs::read_to_string(day.dir.join("input.out.txt")).ok()?;
+    Some(expected.lines().map(|l| l.trim().to_owned()).collect())
+}
+
+fn report(outcome: &Outcome) {
+    let Outcome {
+        day,
+        status,
+        elapsed,
+        verdict,
+        ..
+    } = outcome;
+
+    let verdict = match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail => "FAIL",
+        Verdict::Unknown => "",
+    };
+
+    match status {
+        Status::Ok(answers) => {
+            let answers = answers.join(" ");
+            println!("{day}  {elapsed:>10.2?}  {verdict:<4}  {answers}");
+        }
+        Status::Panicked(msg) => println!("{day}  {elapsed:>10.2?}  {verdict:<4}  panicked: {msg}"),
+        Status::TimedOut => println!("{day}  {elapsed:>10.2?}  {verdict:<4}  timed out"),
+    }
+}
+
+fn summarize(outcomes: &[Outcome], wall: Duration) {
+    let total = outcomes.iter().map(|o| o.elapsed).sum::<Duration>();
+    println!();
+    println!(
+        "{} days in {wall:.2?} (sum of day times: {total:.2?})",
+        outcomes.len()
+    );
+
+    let mut slowest = outcomes.iter().collect::<Vec<_>>();
+    slowest.sort_by_key(|o| std::cmp::Reverse(o.elapsed));
+    let slowest = slowest
+        .iter()
+        .take(3)
+        .map(|o| format!("{} ({:.2?})", o.day, o.elapsed))
+        .collect::<Vec<_>>();
+    println!("slowest: {}", slowest.join(", "));
+
+    let count = |v: Verdict| outcomes.iter().filter(|o| o.verdict == v).count();
+    println!(
+        "passed: {}, failed: {}, unchecked: {}",
+        count(Verdict::Pass),
+        count(Verdict::Fail),
+        count(Verdict::Unknown),
+    );
+}
+
+/// Runs a day on generated inputs of increasing size and prints how its run time grows.
+/// Inputs are kept in `target/scale` for reruns. A day whose generator makes the same input
+/// for different sizes is reported as not scalable after the first one.
+fn scale(root: &Path, day: &Day, sizes: &[usize], seed: u64, timeout: Duration) -> Result<()> {
+    let exe = root.join("target/release").join(day.name());
+    let dir = root.join("target/scale");
+    std::fs::create_dir_all(&dir)?;
+
+    println!("{}", day.label());
+    let mut prev: Option<(usize, Duration)> = None;
+    let mut prev_input: Option<(usize, String)> = None;
+    for &size in sizes {
+        let envs = [(GENERATE_VAR, format!("{size}:{seed}"))];
+        let generated = run_exe(&exe, &envs, timeout)?;
+        match generated.exit {
+            Some(exit) if exit.code() == Some(NO_GENERATOR) => {
+                println!("  no input generator");
+                return Ok(());
+            }
+            Some(exit) if exit.success() => {}
+            _ => {
+                let why = match generated.status() {
+                    Status::TimedOut => "timed out".to_owned(),
+                    Status::Panicked(msg) => msg,
+                    Status::Ok(_) => unreachable!(),
+                };
+                println!("  {size:>10}  generator failed: {why}");
+                return Ok(());
+            }
+        }
+
+        if let Some((s1, prev)) = &prev_input {
+            if *s1 != size && *prev == generated.stdout {
+                println!("  {size:>10}  not scalable: the generator ignores the size");
+                return Ok(());
+            }
+        }
+
+        let input = dir.join(format!("{}-{size}.txt", day.name()));
+        std::fs::write(&input, &generated.stdout)?;
+        prev_input = Some((size, generated.stdout));
+
+        let envs = [(INPUT_VAR, input.display().to_string())];
+        let run = run_exe(&exe, &envs, timeout)?;
+        let elapsed = run.elapsed;
+        match run.status() {
+            Status::Ok(_) => {}
+            Status::Panicked(msg) => {
+                println!("  {size:>10}  panicked: {msg}");
+                return Ok(());
+            }
+            Status::TimedOut => {
+                println!("  {size:>10}  timed out");
+                return Ok(());
+            }
+        }
+
+        // exponent k of the fitted t ~ size^k between consecutive sizes
+        let growth = match prev {
+            Some((s1, t1)) if s1 != size => {
+                let k = (elapsed.as_secs_f64() / t1.as_secs_f64()).ln()
+                    / (size as f64 / s1 as f64).ln();
+                format!("n^{k:.2}")
+            }
+            _ => String::new(),
+        };
+        println!("  {size:>10}  {elapsed:>10.2?}  {growth}");
+        prev = Some((size, elapsed));
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let root = workspace_root();
+
+    let mut days = find_days(&root)?;
+    let Some(year) = args.year.or(days.last().map(|d| d.year)) else {
+        bail!("no days found under {}", root.display());
+    };
+    days.retain(|d| d.year == year);
+    if days.is_empty() {
+        bail!("no days found for {year}");
+    }
+    if !args.days.is_empty() {
+        days.retain(|d| args.days.contains(&d.num));
+    }
+
+    if !args.no_build {
+        build(&root, &days)?;
+    }
+
+    let timeout = Duration::from_secs(args.timeout);
+
+    // run one at a time so the timings aren't skewed by each other
+    if !args.scale.is_empty() {
+        for day in &days {
+            scale(&root, day, &args.scale, args.seed, timeout)?;
+        }
+        return Ok(());
+    }
+
+    // also one at a time, for the same reason
+    if args.bench {
+        let mut results = bench::Results::new();
+        let mut failed = 0;
+        for day in &days {
+            match bench::run(day, &root, args.runs, timeout)? {
+                Some(day_results) => results.extend(day_results),
+                None => failed += 1,
+            }
+        }
+
+        let path = root.join("bench_output.txt");
+        let saved = bench::load(&path)?;
+        let mut baseline = saved.clone().unwrap_or_default();
+        let regressions = failed + bench::compare(&results, &baseline, args.threshold / 100.0);
+
+        // days that weren't run keep their old baseline
+        if args.save_baseline || saved.is_none() {
+            baseline.extend(results);
+            bench::save(&path, &baseline)?;
+            println!("saved baseline to {}", path.display());
+        }
+
+        if regressions > 0 {
+            println!(
+                "{regressions} regression(s): {failed} day(s) failed, the rest beyond {}%",
+                args.threshold
+            );
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let jobs = if args.parallel {
+        args.jobs.unwrap_or(0)
+    } else {
+        1
+    };
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+    let start = Instant::now();
+    let outcomes = pool.install(|| {
+        days.par_iter()
+            .map(|day| run_day(&root, day, timeout, args.part_timeout))
+            .collect::<Result<Vec<_>>>()
+    })?;
+    let wall = start.elapsed();
+
+    if args.json {
+        for part in outcomes.iter().flat_map(|o| &o.parts) {
+            println!("{}", serde_json::to_string(part)?);
+        }
+    } else {
+        for outcome in &outcomes {
+            report(outcome);
+        }
+        summarize(&outcomes, wall);
+    }
+
+    if outcomes.iter().any(|o| o.verdict == Verdict::Fail) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Fetched pages and inputs, kept on disk so reruns don't hit the site.
+//!
+//! Responses live under `$XDG_CACHE_HOME/aoc`, `~/.cache/aoc`, or `%LOCALAPPDATA%\aoc`, as
+//! `YYYY/dayNN/<name>`.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+use crate::puzzle::Puzzle;
+
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if cfg!(windows) => PathBuf::from(std::env::var_os("LOCALAPPDATA")?),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("aoc"))
+}
+
+pub struct Cache {
+    dir: PathBuf,
+    /// Fetch even if there is a cached copy.
+    pub refresh: bool,
+    /// Never fetch.
+    pub offline: bool,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            refresh: false,
+            offline: false,
+        }
+    }
+
+    pub fn path(&self, puzzle: Puzzle, name: &str) -> PathBuf {
+        self.dir.join(puzzle.dir()).join(name)
+    }
+
+    /// The cached `name` for `puzzle`, or what `fetch` returns, which is then cached.
+    pub fn get(
+        &self,
+        puzzle: Puzzle,
+        name: &str,
+        fetch: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let path = self.path(puzzle, name);
+        if !self.refresh {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => return Ok(text),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("could not read {}", path.display()))
+                }
+            }
+        }
+        if self.offline {
+            bail!("{name} for {puzzle} is not cached; fetch it once without --offline");
+        }
+
+        let text = fetch()?;
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| std::fs::write(&path, &text))
+            .with_context(|| format!("could not write {}", path.display()))?;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_cache() {
+    let dir = crate::test_dir::TestDir::new("cache");
+    let puzzle = Puzzle { year: 2023, day: 5 };
+    let mut cache = Cache::new(dir.to_path_buf());
+
+    cache.offline = true;
+    assert!(cache.get(puzzle, "puzzle.html", || unreachable!()).is_err());
+
+    cache.offline = false;
+    let fetched = cache.get(puzzle, "puzzle.html", || Ok("first".into()));
+    assert_eq!(fetched.unwrap(), "first");
+    let cached = cache.get(puzzle, "puzzle.html", || unreachable!());
+    assert_eq!(cached.unwrap(), "first");
+
+    cache.refresh = true;
+    let refreshed = cache.get(puzzle, "puzzle.html", || Ok("second".into()));
+    assert_eq!(refreshed.unwrap(), "second");
+}
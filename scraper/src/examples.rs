@@ -0,0 +1,172 @@
+//! Pulling the example input and its answer out of a puzzle description.
+
+use std::collections::HashSet;
+
+use scraper::ElementRef;
+
+use crate::sel;
+
+/// The text of an element with entities decoded and markup such as `<em>` dropped. Trailing
+/// whitespace is trimmed from each line, and trailing blank lines are dropped, keeping a final
+/// newline for multi-line text.
+pub fn text(element: ElementRef) -> String {
+    let raw = element.text().collect::<String>();
+    let lines = raw.lines().map(str::trim_end).collect::<Vec<_>>();
+    let text = lines.join("\n");
+    let text = text.trim_end_matches('\n');
+    if text.contains('\n') {
+        format!("{text}\n")
+    } else {
+        text.trim().to_owned()
+    }
+}
+
+/// The example's answer, normally the last emphasized code in the text. Emphasis inside the
+/// example blocks, or spanning several words, highlights part of the example instead.
+pub fn answer(part: ElementRef) -> Option<String> {
+    let candidates = part
+        .select(&sel(":not(pre) > code > em"))
+        .map(text)
+        .collect::<Vec<_>>();
+    candidates
+        .iter()
+        .rev()
+        .find(|c| !c.contains(char::is_whitespace))
+        .or(candidates.last())
+        .cloned()
+}
+
+pub struct Ranked<'a> {
+    pub block: ElementRef<'a>,
+    pub score: u32,
+    pub reasons: Vec<&'static str>,
+}
+
+/// The paragraph just before a block's `<pre>`.
+fn lead_in(block: ElementRef) -> Option<String> {
+    let pre = block.parent()?;
+    let prev = pre.prev_siblings().find_map(ElementRef::wrap)?;
+    Some(prev.text().collect())
+}
+
+/// Whether every character of `block` also appears in `input`, and whether both are grids (all
+/// lines the same width) or neither is.
+fn shape_matches(block: &str, input: &str) -> (bool, bool) {
+    let chars = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<HashSet<_>>()
+    };
+    let is_grid = |s: &str| {
+        let mut widths = s.lines().map(str::len);
+        let first = widths.next();
+        widths.all(|w| Some(w) == first)
+    };
+    (
+        chars(block).is_subset(&chars(input)),
+        is_grid(block) == is_grid(input),
+    )
+}
+
+/// Scores each block, best first. Blocks that score the same stay in page order.
+pub fn rank<'a>(blocks: Vec<ElementRef<'a>>, input: Option<&str>) -> Vec<Ranked<'a>> {
+    let mut seen_multi_line = false;
+    let mut ranked = blocks
+        .into_iter()
+        .map(|block| {
+            let text = block.text().collect::<String>();
+            let mut ranked = Ranked {
+                block,
+                score: 0,
+                reasons: vec![],
+            };
+            let mut add = |score, reason| {
+                ranked.score += score;
+                ranked.reasons.push(reason);
+            };
+
+            if text.trim().lines().count() > 1 {
+                add(1, "multi-line");
+                if !seen_multi_line {
+                    seen_multi_line = true;
+                    add(2, "first multi-line block");
+                }
+            }
+            if lead_in(block).is_some_and(|p| p.to_lowercase().contains("for example")) {
+                add(3, "preceded by \"For example\"");
+            }
+            if let Some(input) = input {
+                let (chars, grid) = shape_matches(text.trim(), input.trim());
+                if chars {
+                    add(2, "same characters as the real input");
+                    if grid {
+                        add(1, "same line layout as the real input");
+                    }
+                }
+            }
+            ranked
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.score));
+    ranked
+}
+
+#[cfg(test)]
+#[test]
+fn test_text() {
+    /// The first example block and the answer of a saved description.
+    fn extract(html: &str) -> (String, Option<String>) {
+        let html = scraper::Html::parse_fragment(html);
+        let part = html.select(&sel(".day-desc")).next().unwrap();
+        let block = part.select(&sel("pre > code")).next().unwrap();
+        (text(block), answer(part))
+    }
+
+    let (block, answer) = extract(include_str!("../fixtures/day19.html"));
+    assert_eq!(
+        block,
+        "px{a<2006:qkq,m>2090:A,rhg}\npv{a>1716:R,A}\nin{s<1351:px,qqz}\n\n\
+         {x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=496}\n"
+    );
+    assert_eq!(answer.as_deref(), Some("19114"));
+
+    let (block, answer) = extract(include_str!("../fixtures/day20.html"));
+    assert_eq!(block, "broadcaster -> a, b, c\n%a -> b\n&inv -> a\n");
+    assert_eq!(answer.as_deref(), Some("32000000"));
+
+    let (block, answer) = extract(include_str!("../fixtures/day10.html"));
+    assert_eq!(block, ".....\n.S-7.\n.|.|.\n");
+    assert_eq!(answer.as_deref(), Some("4"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_rank() {
+    use scraper::Html;
+
+    let html = Html::parse_fragment(
+        r#"<article class="day-desc">
+        <p>The engine uses <code>.</code> for blanks:</p>
+        <pre><code>a single line</code></pre>
+        <p>Here is a note:</p>
+        <pre><code>x=1
+y=2
+</code></pre>
+        <p>For example, consider this schematic:</p>
+        <pre><code>467..114..
+...*......
+</code></pre>
+        </article>"#,
+    );
+    let blocks = html.select(&sel("pre > code")).collect();
+    let ranked = rank(blocks, Some("1.3*..\n..45*.\n"));
+
+    let texts = ranked
+        .iter()
+        .map(|r| r.block.text().collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(texts[0], "467..114..\n...*......\n");
+    assert_eq!(texts[1], "x=1\ny=2\n");
+    assert_eq!(texts[2], "a single line");
+}
@@ -0,0 +1,169 @@
+//! Every answer submitted for a puzzle, and what the site said about it.
+//!
+//! Kept as `YYYY/dayNN/submissions.txt` under the data directory (`$XDG_DATA_HOME/aoc`,
+//! `~/.local/share/aoc`, or `%APPDATA%\aoc`) rather than the cache, since clearing it would lose
+//! the bounds and the record of rejected answers. One tab-separated line per submission: time,
+//! part, outcome (see [`Outcome::tag`]), answer.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::puzzle::Puzzle;
+use crate::submit::Outcome;
+
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if cfg!(windows) => PathBuf::from(std::env::var_os("APPDATA")?),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("aoc"))
+}
+
+/// The history file for `puzzle` under `data_dir`.
+pub fn path(data_dir: &Path, puzzle: Puzzle) -> PathBuf {
+    data_dir.join(puzzle.dir()).join("submissions.txt")
+}
+
+pub struct Entry {
+    pub time: DateTime<Utc>,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+        };
+
+        let mut entries = vec![];
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let entry = (|| {
+                let mut fields = line.splitn(4, '\t');
+                Some(Entry {
+                    time: fields.next()?.parse().ok()?,
+                    part: fields.next()?.parse().ok()?,
+                    outcome: Outcome::from_tag(fields.next()?)?,
+                    answer: fields.next()?.to_owned(),
+                })
+            })();
+            entries.push(
+                entry.with_context(|| format!("malformed line in {}: {line}", path.display()))?,
+            );
+        }
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self, part: u8) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |e| e.part == part)
+    }
+
+    /// Appends a submission to the log on disk.
+    pub fn record(&mut self, entry: Entry) -> Result<()> {
+        let line = format!(
+            "{}\t{}\t{}\t{}\n",
+            entry.time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            entry.part,
+            entry.outcome.tag(),
+            entry.answer
+        );
+        std::fs::create_dir_all(self.path.parent().unwrap())
+            .and_then(|()| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?
+                    .write_all(line.as_bytes())
+            })
+            .with_context(|| format!("could not write {}", self.path.display()))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// The tightest bounds from "too low" and "too high" answers: the answer is above the
+    /// first and below the second.
+    pub fn bounds(&self, part: u8) -> (Option<i128>, Option<i128>) {
+        let numeric = |outcome: Outcome| {
+            self.entries(part)
+                .filter(move |e| e.outcome == outcome)
+                .filter_map(|e| e.answer.parse::<i128>().ok())
+        };
+        (
+            numeric(Outcome::TooLow).max(),
+            numeric(Outcome::TooHigh).min(),
+        )
+    }
+
+    /// Fails if `answer` is known to be wrong.
+    pub fn check(&self, part: u8, answer: &str) -> Result<()> {
+        if let Some(e) = self
+            .entries(part)
+            .find(|e| e.answer == answer && e.outcome.rejected())
+        {
+            bail!(
+                "{answer} was already rejected at {} ({})",
+                e.time,
+                e.outcome
+            );
+        }
+
+        let Ok(n) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        match self.bounds(part) {
+            (Some(low), _) if n <= low => bail!("{answer} is not above {low}, which is too low"),
+            (_, Some(high)) if n >= high => {
+                bail!("{answer} is not below {high}, which is too high")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_history() {
+    let dir = crate::test_dir::TestDir::new("history");
+    let path = path(&dir, Puzzle { year: 2023, day: 5 });
+    assert_eq!(path, dir.join("2023/day05/submissions.txt"));
+    let mut history = History::load(path.clone()).unwrap();
+
+    let results = [
+        (1, Outcome::TooLow, "100"),
+        (1, Outcome::TooHigh, "500"),
+        (1, Outcome::TooLow, "200"),
+        (1, Outcome::Wrong, "300"),
+        (2, Outcome::TooHigh, "50"),
+    ];
+    for (part, outcome, answer) in results {
+        let answer = answer.into();
+        let entry = Entry {
+            time: Utc::now(),
+            part,
+            outcome,
+            answer,
+        };
+        history.record(entry).unwrap();
+    }
+
+    let history = History::load(path).unwrap();
+    assert_eq!(history.bounds(1), (Some(200), Some(500)));
+    assert!(history.check(1, "150").is_err());
+    assert!(history.check(1, "300").is_err());
+    assert!(history.check(1, "500").is_err());
+    assert!(history.check(1, "499").is_ok());
+    assert!(history.check(1, "abc").is_ok());
+    assert!(history.check(2, "200").is_err());
+}
@@ -0,0 +1,323 @@
+//! Requests to the site, authenticated with the session cookie.
+//!
+//! Requests are spaced at least [`MIN_INTERVAL`] apart, also across runs: the time of the last
+//! one is kept in the cache directory. Transport errors and 5xx responses to GETs are retried
+//! with exponential backoff; 4xx responses never are, and neither are POSTs, since a submission
+//! that failed on the way back may still have been judged.
+//!
+//! Responses that only say the session has expired, the puzzle is locked, or there is no such
+//! page become a [`Refusal`], whatever their status, so they never pass for content. That
+//! decision is made by [`interpret`], also for responses replayed by [`crate::replay`].
+//!
+//! The user agent names this repository and, per the site's request, a way to contact whoever
+//! runs it, from `AOC_CONTACT` or `contact` in the config directory.
+
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{replay, session};
+
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Attempts per GET, and the wait before the first retry, which doubles after each.
+const ATTEMPTS: u32 = 4;
+const BACKOFF: Duration = Duration::from_secs(1);
+
+/// Contact details for the user agent, if configured.
+fn contact() -> Result<Option<String>> {
+    if let Ok(contact) = std::env::var(CONTACT_VAR) {
+        return Ok(Some(contact.trim().to_owned()));
+    }
+    let Some(path) = session::config_dir().map(|dir| dir.join("contact")) else {
+        return Ok(None);
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contact) => Ok(Some(contact.trim().to_owned())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("could not read {}", path.display())),
+    }
+}
+
+fn user_agent(contact: Option<&str>) -> String {
+    let name = "The0x539's AoC scraper (https://github.com/The0x539/aoc2023";
+    match contact {
+        Some(contact) if !contact.is_empty() => format!("{name}; {contact})"),
+        _ => format!("{name})"),
+    }
+}
+
+/// Whether a failed attempt is worth repeating.
+fn retryable(result: &reqwest::Result<Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(e) => e.status().is_none_or(|s| s.is_server_error()),
+    }
+}
+
+/// Why the site didn't serve a page, for the cases worth telling apart.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    /// The session token is missing, expired or otherwise not accepted.
+    LoggedOut,
+    /// The puzzle exists but hasn't unlocked yet.
+    Locked,
+    NotFound,
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::LoggedOut => {
+                "adventofcode.com rejected the session token; it has probably expired, so log \
+                 in again and copy the new `session` cookie"
+            }
+            Self::Locked => "the puzzle hasn't unlocked yet",
+            Self::NotFound => "the site has no such page (404 Not Found)",
+        })
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+impl Refusal {
+    /// What a response amounts to, if it isn't what was asked for. `auth_redirect` is whether
+    /// the request ended up on a login page.
+    fn of(status: StatusCode, auth_redirect: bool, body: &str) -> Option<Self> {
+        // a bad token gets 400 (for inputs), a redirect to login, or a page asking to log in
+        if auth_redirect
+            || status == StatusCode::BAD_REQUEST
+            || body.contains("Please log in")
+            || body.contains("please identify yourself")
+            || body.contains("/auth/login\"")
+        {
+            Some(Self::LoggedOut)
+        } else if status == StatusCode::NOT_FOUND && body.contains("before it unlocks") {
+            Some(Self::Locked)
+        } else if status == StatusCode::NOT_FOUND {
+            Some(Self::NotFound)
+        } else {
+            None
+        }
+    }
+}
+
+/// A response as the site sent it, before [`interpret`] decides what it means.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Raw {
+    pub status: u16,
+    /// The URL the response came from, after any redirects.
+    pub url: String,
+    pub body: String,
+}
+
+/// The body of a response that has the page asked for, or why it doesn't.
+pub fn interpret(raw: Raw) -> Result<String> {
+    let status = StatusCode::from_u16(raw.status).context("invalid status")?;
+    if let Some(refusal) = Refusal::of(status, raw.url.contains("/auth/"), &raw.body) {
+        return Err(refusal.into());
+    }
+    if status.is_client_error() || status.is_server_error() {
+        bail!("http error: {status} for {}", raw.url);
+    }
+    Ok(raw.body)
+}
+
+/// Where responses come from: [`Http`], a recording of it (see [`crate::replay`]), or canned
+/// responses in tests.
+pub trait Backend {
+    /// GETs `path`, e.g. `/2023/day/5/input`.
+    fn get(&self, path: &str) -> Result<String>;
+
+    /// POSTs `form` to `path`.
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String>;
+}
+
+pub struct Http {
+    client: Client,
+    cookie: String,
+    base_url: String,
+    user_agent: String,
+    /// Where the time of the last request is kept, if anywhere.
+    last_request: Option<PathBuf>,
+    pub min_interval: Duration,
+    /// Where to save every exchange for `--replay`, if anywhere.
+    pub record: Option<PathBuf>,
+}
+
+impl Http {
+    /// `token` is the session token; see [`session::load`].
+    pub fn new(base_url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: Client::new(),
+            cookie: format!("session={token}"),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            user_agent: user_agent(contact()?.as_deref()),
+            last_request: crate::cache::cache_dir().map(|dir| dir.join("last-request")),
+            min_interval: MIN_INTERVAL,
+            record: None,
+        })
+    }
+
+    /// GETs `path`, e.g. `/2023/day/5/input`.
+    pub fn get(&self, path: &str) -> Result<String> {
+        self.exchange("GET", path, &[], ATTEMPTS, || {
+            self.client.get(self.base_url.clone() + path)
+        })
+    }
+
+    /// POSTs `form` to `path`, once.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.exchange("POST", path, form, 1, || {
+            self.client.post(self.base_url.clone() + path).form(form)
+        })
+    }
+
+    /// Waits out the rest of the minimum interval since the last request, then notes the time.
+    fn throttle(&self) {
+        let Some(path) = &self.last_request else {
+            return;
+        };
+        let last = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|ms| ms.trim().parse().ok())
+            .map(|ms| SystemTime::UNIX_EPOCH + Duration::from_millis(ms));
+        if let Some(last) = last {
+            let since = SystemTime::now().duration_since(last).unwrap_or_default();
+            if since < self.min_interval {
+                sleep(self.min_interval - since);
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        // best effort: failing to record the time shouldn't stop the request
+        let _ = std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| std::fs::write(path, now.as_millis().to_string()));
+    }
+
+    fn exchange(
+        &self,
+        method: &str,
+        path: &str,
+        form: &[(&str, &str)],
+        attempts: u32,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<String> {
+        let raw = self.send(attempts, request);
+        // the request has already been made, so a failed recording must not hide its response
+        if let Some(dir) = &self.record {
+            if let Err(e) = replay::record(dir, method, path, form, &raw) {
+                eprintln!("warning: {e:#}");
+            }
+        }
+        interpret(raw?)
+    }
+
+    fn send(&self, attempts: u32, request: impl Fn() -> RequestBuilder) -> Result<Raw> {
+        let mut backoff = BACKOFF;
+        let mut attempt = 1;
+        let response = loop {
+            self.throttle();
+            let result = request()
+                .header("Cookie", &self.cookie)
+                .header("User-Agent", &self.user_agent)
+                .send();
+            if attempt >= attempts || !retryable(&result) {
+                break result.context("transport error")?;
+            }
+            match &result {
+                Ok(response) => eprintln!("{}, retrying in {backoff:?}", response.status()),
+                Err(e) => eprintln!("{e}, retrying in {backoff:?}"),
+            }
+            sleep(backoff);
+            backoff *= 2;
+            attempt += 1;
+        };
+
+        Ok(Raw {
+            status: response.status().as_u16(),
+            url: response.url().to_string(),
+            body: response.text().context("body error")?,
+        })
+    }
+}
+
+impl Backend for Http {
+    fn get(&self, path: &str) -> Result<String> {
+        Http::get(self, path)
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        Http::post(self, path, form)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_refusal() {
+    let of = |status, body| Refusal::of(StatusCode::from_u16(status).unwrap(), false, body);
+    assert_eq!(
+        of(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ),
+        Some(Refusal::LoggedOut)
+    );
+    assert_eq!(
+        of(
+            200,
+            r#"<a href="/2023/auth/login">[Log In]</a><article>...</article>"#
+        ),
+        Some(Refusal::LoggedOut)
+    );
+    assert_eq!(
+        of(
+            200,
+            "<p>To play, please identify yourself via one of these services:</p>"
+        ),
+        Some(Refusal::LoggedOut)
+    );
+    assert_eq!(
+        Refusal::of(StatusCode::OK, true, "<html></html>"),
+        Some(Refusal::LoggedOut)
+    );
+    assert_eq!(
+        of(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar \
+                 countdown is synchronized with the server time."
+        ),
+        Some(Refusal::Locked)
+    );
+    assert_eq!(of(404, "404 Not Found\n"), Some(Refusal::NotFound));
+    assert_eq!(
+        of(200, r#"<a href="/2023/auth/logout">[Log Out]</a>"#),
+        None
+    );
+    assert_eq!(of(500, "oops"), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_user_agent() {
+    assert_eq!(
+        user_agent(Some("me@example.com")),
+        "The0x539's AoC scraper (https://github.com/The0x539/aoc2023; me@example.com)"
+    );
+    assert_eq!(
+        user_agent(None),
+        "The0x539's AoC scraper (https://github.com/The0x539/aoc2023)"
+    );
+}
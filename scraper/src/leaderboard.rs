@@ -0,0 +1,287 @@
+//! `scraper leaderboard`: a private leaderboard, ranked, with changes since the last look.
+//!
+//! The site asks that its leaderboard JSON be fetched at most every [`POLL_INTERVAL`], so the
+//! last response is cached as `YYYY/leaderboard-<id>.json` and reused until it is that old.
+//! The response before it is kept as `leaderboard-<id>.prev.json` to compare against.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::http::Backend;
+use crate::puzzle::Puzzle;
+
+pub const LEADERBOARD_VAR: &str = "AOC_LEADERBOARD";
+
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Deserialize)]
+pub struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    last_star_ts: i64,
+    /// Day, then part.
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn stars_on(&self, day: u8) -> usize {
+        self.completion_day_level.get(&day).map_or(0, |d| d.len())
+    }
+}
+
+impl Leaderboard {
+    /// Members by score, ties going to whoever got their last star first.
+    fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+}
+
+pub struct Fetched {
+    pub current: Leaderboard,
+    pub previous: Option<Leaderboard>,
+    /// When the site may be asked again, if the cached copy was used.
+    pub next_poll: Option<Duration>,
+}
+
+fn parse(text: &str, source: &Path) -> Result<Leaderboard> {
+    serde_json::from_str(text)
+        .with_context(|| format!("malformed leaderboard in {}", source.display()))
+}
+
+/// The leaderboard from the cache if it is recent enough as of `now`, otherwise from
+/// `backend`.
+pub fn fetch(
+    backend: &dyn Backend,
+    cache_dir: &Path,
+    year: u16,
+    id: &str,
+    now: SystemTime,
+) -> Result<Fetched> {
+    let path = cache_dir.join(format!("{year}/leaderboard-{id}.json"));
+    let prev_path = path.with_extension("prev.json");
+    let read = |p: &Path| match std::fs::read_to_string(p) {
+        Ok(text) => parse(&text, p).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("could not read {}", p.display())),
+    };
+
+    let age = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|t| now.duration_since(t).unwrap_or_default());
+    if let Some(age) = age.filter(|&age| age < POLL_INTERVAL) {
+        if let Some(current) = read(&path)? {
+            return Ok(Fetched {
+                current,
+                previous: read(&prev_path)?,
+                next_poll: Some(POLL_INTERVAL - age),
+            });
+        }
+    }
+
+    let text = backend.get(&format!("/{year}/leaderboard/private/view/{id}.json"))?;
+    let current = parse(&text, Path::new("the response"))?;
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    if path.exists() {
+        std::fs::rename(&path, &prev_path)?;
+    }
+    std::fs::write(&path, text).with_context(|| format!("could not write {}", path.display()))?;
+
+    Ok(Fetched {
+        current,
+        previous: read(&prev_path)?,
+        next_poll: None,
+    })
+}
+
+fn signed(n: i64) -> String {
+    match n {
+        0 => String::new(),
+        n => format!("{n:+}"),
+    }
+}
+
+/// Time since the puzzle unlocked, as `H:MM:SS`, with days in front if needed.
+fn since_unlock(puzzle: Puzzle, ts: i64) -> String {
+    let secs = (ts - puzzle.unlocks_at().timestamp()).max(0);
+    let (days, h, m, s) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    match days {
+        0 => format!("{h}:{m:02}:{s:02}"),
+        d => format!("{d}d {h:02}:{m:02}:{s:02}"),
+    }
+}
+
+/// The ranked table, with one column per day (`*` for both stars, `+` for one), followed by
+/// each member's star times on `day`, or the latest day anyone has a star on.
+pub fn render(
+    year: u16,
+    current: &Leaderboard,
+    previous: Option<&Leaderboard>,
+    day: Option<u8>,
+) -> String {
+    let ranked = current.ranked();
+    let last_day = ranked
+        .iter()
+        .flat_map(|m| m.completion_day_level.keys())
+        .copied()
+        .max()
+        .unwrap_or(1);
+    let prev_rank = previous.map(|p| {
+        p.ranked()
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.id, (i, m.local_score, m.stars)))
+            .collect::<HashMap<_, _>>()
+    });
+
+    let mut out = String::new();
+    let days = 1..=last_day;
+    let pad = " ".repeat(25);
+    let tens = days
+        .clone()
+        .map(|d| char::from(b'0' + d / 10))
+        .collect::<String>();
+    let ones = days
+        .clone()
+        .map(|d| char::from(b'0' + d % 10))
+        .collect::<String>();
+    if last_day >= 10 {
+        writeln!(out, "{pad}{}", tens.replace('0', " ")).unwrap();
+    }
+    writeln!(out, "{pad}{ones}").unwrap();
+
+    for (rank, member) in ranked.iter().enumerate() {
+        let (score, stars, moved) = match prev_rank.as_ref().map(|p| p.get(&member.id)) {
+            None => (String::new(), String::new(), String::new()),
+            Some(None) => (String::new(), String::new(), "new".to_owned()),
+            Some(Some(&(was, score, stars))) => (
+                signed(i64::from(member.local_score) - i64::from(score)),
+                signed(i64::from(member.stars) - i64::from(stars)),
+                match rank.cmp(&was) {
+                    std::cmp::Ordering::Less => format!("up {}", was - rank),
+                    std::cmp::Ordering::Greater => format!("down {}", rank - was),
+                    std::cmp::Ordering::Equal => String::new(),
+                },
+            ),
+        };
+        let cells = days
+            .clone()
+            .map(|d| match member.stars_on(d) {
+                2 => '*',
+                1 => '+',
+                _ => '.',
+            })
+            .collect::<String>();
+        let row = format!(
+            "{:>3}) {:>5} {score:<5} {:>2}* {stars:<3} {cells}  {}  {moved}",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            member.name(),
+        );
+        writeln!(out, "{}", row.trim_end()).unwrap();
+    }
+
+    let day = day.unwrap_or(last_day);
+    let puzzle = Puzzle { year, day };
+    writeln!(out, "\n{puzzle}:").unwrap();
+    let mut solvers = ranked
+        .iter()
+        .filter_map(|m| Some((m, m.completion_day_level.get(&day)?)))
+        .collect::<Vec<_>>();
+    solvers.sort_by_key(|(_, parts)| {
+        let ts = |p| parts.get(&p).map_or(i64::MAX, |s: &Star| s.get_star_ts);
+        (ts(2), ts(1))
+    });
+    for (member, parts) in solvers {
+        let time = |p| {
+            parts
+                .get(&p)
+                .map_or("-".into(), |s: &Star| since_unlock(puzzle, s.get_star_ts))
+        };
+        writeln!(
+            out,
+            "  {:<24} part 1 {:>12}  part 2 {:>12}",
+            member.name(),
+            time(1),
+            time(2)
+        )
+        .unwrap();
+    }
+    if out.ends_with(":\n") {
+        writeln!(out, "  no stars yet").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+#[test]
+fn test_leaderboard() {
+    use std::cell::Cell;
+
+    struct Fixture<'a> {
+        responses: Vec<&'a str>,
+        calls: Cell<usize>,
+    }
+    impl Backend for Fixture<'_> {
+        fn get(&self, path: &str) -> Result<String> {
+            assert_eq!(path, "/2023/leaderboard/private/view/42.json");
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.responses[self.calls.get() - 1].to_owned())
+        }
+        fn post(&self, _: &str, _: &[(&str, &str)]) -> Result<String> {
+            unreachable!()
+        }
+    }
+
+    let backend = Fixture {
+        responses: vec![
+            include_str!("../fixtures/leaderboard-1.json"),
+            include_str!("../fixtures/leaderboard-2.json"),
+        ],
+        calls: Cell::new(0),
+    };
+    let dir = crate::test_dir::TestDir::new("leaderboard");
+    let now = SystemTime::now();
+
+    let first = fetch(&backend, &dir, 2023, "42", now).unwrap();
+    assert!(first.previous.is_none() && first.next_poll.is_none());
+    let cached = fetch(&backend, &dir, 2023, "42", now + Duration::from_secs(60)).unwrap();
+    assert_eq!(backend.calls.get(), 1);
+    // the cache file is written a moment after `now`
+    let next_poll = cached.next_poll.unwrap();
+    assert!(next_poll > Duration::from_secs(13 * 60) && next_poll <= Duration::from_secs(14 * 60));
+    let second = fetch(&backend, &dir, 2023, "42", now + POLL_INTERVAL * 2).unwrap();
+    assert_eq!(backend.calls.get(), 2);
+
+    assert_eq!(
+        render(2023, &second.current, second.previous.as_ref(), None),
+        include_str!("../fixtures/leaderboard.txt")
+    );
+}
@@ -1,9 +1,193 @@
-use std::io::Write;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
-use reqwest::blocking::Client;
+use clap::{Parser, Subcommand};
 use scraper::{ElementRef, Html, Selector};
 
+mod cache;
+mod examples;
+mod history;
+mod http;
+mod leaderboard;
+mod markdown;
+mod puzzle;
+mod replay;
+mod scaffold;
+mod session;
+mod submit;
+mod sync;
+#[cfg(test)]
+mod test_dir;
+mod wait;
+
+use cache::Cache;
+use history::History;
+use http::{Backend, Http};
+use puzzle::Puzzle;
+
+/// Fetches a puzzle's example and real input into its day crate.
+///
+/// Run from a `YYYY/dayNN` directory, the puzzle and output directory are taken from the path.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(flatten)]
+    select: Select,
+
+    /// Where to write the input files (defaults to the current directory if it is the day's
+    /// crate, otherwise `YYYY/dayNN` under it)
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Fetch the page and input again even if they are cached
+    #[arg(long)]
+    refresh: bool,
+
+    /// Only use cached pages: rewrite `test.txt` and `test.out.txt`, leaving `input.txt` alone
+    #[arg(long, conflicts_with = "refresh")]
+    offline: bool,
+
+    /// Pick the most likely example block instead of asking
+    #[arg(long)]
+    non_interactive: bool,
+
+    /// Once part 1 is solved: fetch the page again and fill in part 2's expected answer, and
+    /// `test2.txt` if part 2 brings its own example, leaving the input and part 1 files alone
+    #[arg(short, long)]
+    update: bool,
+
+    /// If the puzzle isn't out yet, count down to it and fetch a few seconds after it unlocks
+    #[arg(short, long)]
+    wait: bool,
+
+    #[command(flatten)]
+    site: Site,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Submits an answer and reports whether it was right
+    Submit {
+        #[command(flatten)]
+        select: Select,
+
+        /// Which part the answer is for
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// The answer (defaults to what the day's binary prints for the part)
+        answer: Option<String>,
+
+        /// Submit even if the answer was already rejected or is outside the known bounds
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Creates a day crate from `template/` and adds it to the workspace
+    NewDay {
+        #[command(flatten)]
+        select: Select,
+
+        /// Fetch the new day's inputs right away
+        #[arg(short, long)]
+        fetch: bool,
+    },
+
+    /// Fetches the missing inputs and examples of every day in the workspace
+    ///
+    /// Examples are picked without asking, as with `--non-interactive`.
+    Sync,
+
+    /// Shows a private leaderboard, with changes since it was last fetched
+    Leaderboard {
+        /// The leaderboard's id, from its URL
+        #[arg(long, env = leaderboard::LEADERBOARD_VAR)]
+        id: String,
+
+        /// Year of the event (defaults to the latest year)
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Day to show star times for (defaults to the latest day with stars)
+        #[arg(short, long)]
+        day: Option<u8>,
+
+        /// Keep showing it, fetching again as often as the site allows
+        #[arg(short, long)]
+        watch: bool,
+    },
+}
+
+#[derive(clap::Args)]
+struct Select {
+    /// Year of the puzzle (defaults to the directory's parent, or the latest year)
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Day of the puzzle, 1 to 25 (defaults to the directory name)
+    #[arg(short, long)]
+    day: Option<u8>,
+}
+
+impl Select {
+    /// The selected puzzle, which might not be out yet.
+    fn resolve(&self, cwd: &Path) -> Result<Puzzle> {
+        let here = puzzle::infer(cwd);
+        let day = match (self.day, &here) {
+            (Some(day), _) => day,
+            (None, Ok((_, day))) => *day,
+            (None, Err(e)) => bail!("no --day given, and cannot infer it from the cwd: {e}"),
+        };
+        let year = self
+            .year
+            .or(here.as_ref().ok().and_then(|h| h.0))
+            .unwrap_or_else(puzzle::what_year_is_it);
+
+        let puzzle = Puzzle { year, day };
+        puzzle.check()?;
+        Ok(puzzle)
+    }
+}
+
+#[derive(clap::Args)]
+struct Site {
+    /// Session token, if neither `AOC_SESSION` nor the config file has one
+    #[arg(long, global = true)]
+    session: Option<String>,
+
+    /// Where the site is, e.g. a local stub server for testing
+    #[arg(long, global = true, env = http::BASE_URL_VAR, default_value = http::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// Save every request and its response in this directory, for `--replay`
+    #[arg(long, global = true, value_name = "DIR")]
+    record: Option<PathBuf>,
+
+    /// Answer requests from a `--record`ed directory instead of the site
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "record")]
+    replay: Option<PathBuf>,
+}
+
+impl Site {
+    fn connect(&self) -> Result<Box<dyn Backend>> {
+        if let Some(dir) = &self.replay {
+            return Ok(Box::new(replay::Replay::new(dir.clone())));
+        }
+        let token = session::load(self.session.as_deref())?;
+        let mut http = Http::new(&self.base_url, &token)?;
+        http.record = self.record.clone();
+        Ok(Box::new(http))
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
 fn prompt(msg: &str) -> Result<bool> {
     let mut input = String::new();
     loop {
@@ -12,7 +196,7 @@ fn prompt(msg: &str) -> Result<bool> {
 
         input.clear();
         std::io::stdin().read_line(&mut input)?;
-        match input.as_bytes().get(0) {
+        match input.as_bytes().first() {
             Some(b'y' | b'Y') => return Ok(true),
             Some(b'n' | b'N') => return Ok(false),
             _ => continue,
@@ -20,18 +204,36 @@ fn prompt(msg: &str) -> Result<bool> {
     }
 }
 
-fn pick_block(blocks: Vec<ElementRef>) -> Result<ElementRef> {
+fn pick_block<'a>(
+    blocks: Vec<ElementRef<'a>>,
+    input: Option<&str>,
+    non_interactive: bool,
+) -> Result<ElementRef<'a>> {
     if blocks.is_empty() {
         bail!("no code blocks");
     } else if blocks.len() == 1 {
         return Ok(blocks.into_iter().next().unwrap());
     }
 
-    for block in blocks {
-        println!("{}", block.inner_html());
+    let ranked = examples::rank(blocks, input);
+    if non_interactive {
+        let best = &ranked[0];
+        let reasons = match &best.reasons[..] {
+            [] => "nothing stood out, so it is the first block".to_owned(),
+            reasons => reasons.join(", "),
+        };
+        println!(
+            "picked the example input with score {}: {reasons}",
+            best.score
+        );
+        return Ok(best.block);
+    }
+
+    for ranked in ranked {
+        print!("{}", examples::text(ranked.block));
 
         if prompt("is this the test input?")? {
-            return Ok(block);
+            return Ok(ranked.block);
         }
     }
     bail!("no match");
@@ -41,72 +243,195 @@ fn sel(s: &str) -> Selector {
     Selector::parse(s).unwrap()
 }
 
-fn get_test_output(part: ElementRef) -> Option<String> {
-    part.select(&sel("code > em"))
-        .last()
-        .map(|n| n.inner_html())
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let cwd = std::env::current_dir().context("no cwd")?;
+
+    match &args.command {
+        None => {
+            let puzzle = args.select.resolve(&cwd)?;
+            let out = match &args.out {
+                Some(out) => out.clone(),
+                None if puzzle::infer(&cwd).is_ok_and(|h| {
+                    h == (Some(puzzle.year), puzzle.day) || h == (None, puzzle.day)
+                }) =>
+                {
+                    cwd.clone()
+                }
+                None => cwd.join(puzzle.dir()),
+            };
+            if !out.is_dir() {
+                bail!("{} is not a directory; pass --out", out.display());
+            }
+            fetch(&args, puzzle, &out, false).map(drop)
+        }
+        Some(Command::Leaderboard {
+            id,
+            year,
+            day,
+            watch,
+        }) => {
+            let year = year.unwrap_or_else(puzzle::what_year_is_it);
+            let http = args.site.connect()?;
+            let cache_dir = cache::cache_dir().context("no cache directory")?;
+            loop {
+                let now = std::time::SystemTime::now();
+                let fetched = leaderboard::fetch(&*http, &cache_dir, year, id, now)?;
+                let previous = fetched.previous.as_ref();
+                print!(
+                    "{}",
+                    leaderboard::render(year, &fetched.current, previous, *day)
+                );
+                if let Some(wait) = fetched.next_poll {
+                    println!(
+                        "\n(cached; it can be fetched again in {}m)",
+                        wait.as_secs() / 60 + 1
+                    );
+                }
+                if !watch {
+                    return Ok(());
+                }
+                std::thread::sleep(fetched.next_poll.unwrap_or(leaderboard::POLL_INTERVAL));
+                println!();
+            }
+        }
+        Some(Command::NewDay {
+            select,
+            fetch: and_fetch,
+        }) => {
+            let puzzle = select.resolve(&cwd)?;
+            let dir = scaffold::new_day(workspace_root(), puzzle)?;
+            println!("created {}", dir.display());
+            if *and_fetch {
+                fetch(&args, puzzle, &dir, false)?;
+            }
+            Ok(())
+        }
+        Some(Command::Sync) => {
+            let root = workspace_root();
+            let days = sync::days(root)?;
+            let statuses = sync::sync(&days, chrono::Utc::now(), |day| {
+                println!("fetching {}", day.puzzle);
+                fetch(&args, day.puzzle, &day.dir, true)
+            });
+            print!("\n{}", sync::render(root, &days, &statuses));
+            let failed = statuses
+                .iter()
+                .filter(|s| matches!(s, sync::Status::Failed(_)))
+                .count();
+            if failed > 0 {
+                bail!("{failed} of {} days failed", days.len());
+            }
+            Ok(())
+        }
+        Some(Command::Submit {
+            select,
+            part,
+            answer,
+            force,
+        }) => {
+            let puzzle = select.resolve(&cwd)?;
+            puzzle.validate(chrono::Utc::now())?;
+            let answer = match answer {
+                Some(answer) => answer.clone(),
+                None => submit::solve(workspace_root(), puzzle, *part)?,
+            };
+
+            let data_dir = history::data_dir().context("no data directory")?;
+            let mut history = History::load(history::path(&data_dir, puzzle))?;
+            if let Err(e) = history.check(*part, &answer) {
+                if !force {
+                    bail!("{e}; pass --force to submit anyway");
+                }
+                println!("warning: {e}");
+            }
+
+            println!("submitting {answer} for {puzzle} part {part}");
+            let outcome = submit::submit(&*args.site.connect()?, puzzle, *part, &answer)?;
+            println!("{outcome}");
+
+            let correct = outcome == submit::Outcome::Correct;
+            history.record(history::Entry {
+                time: chrono::Utc::now(),
+                part: *part,
+                outcome,
+                answer,
+            })?;
+            if !correct {
+                match history.bounds(*part) {
+                    (None, None) => {}
+                    (low, high) => println!(
+                        "the answer is in {}..{}",
+                        low.map_or(String::new(), |n| (n + 1).to_string()),
+                        high.map_or(String::new(), |n| n.to_string()),
+                    ),
+                }
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
 }
 
-fn what_year_is_it() -> i32 {
-    use chrono::prelude::*;
-    let tz = FixedOffset::west_opt(5 * 3600).unwrap();
-    let date = Utc::now().with_timezone(&tz);
-    if date.month() == 12 {
-        date.year()
+/// Fetches the puzzle's page and input and writes the day's files to `out`. With
+/// `missing_only`, files that already exist (other than placeholders) are left alone and the example is picked without
+/// asking. Returns the names of the files written.
+fn fetch(args: &Args, puzzle: Puzzle, out: &Path, missing_only: bool) -> Result<Vec<&'static str>> {
+    if args.wait {
+        let redraw = std::io::stderr().is_terminal();
+        wait::until_unlocked(puzzle, &wait::SystemClock, &mut std::io::stderr(), redraw)?;
     } else {
-        // grab from last year
-        date.year() - 1
+        puzzle.validate(chrono::Utc::now())?;
     }
-}
 
-fn main() -> Result<()> {
-    let cwd = std::env::current_dir().context("no cwd")?;
+    let mut cache = Cache::new(cache::cache_dir().context("no cache directory")?);
+    cache.refresh = args.refresh;
+    cache.offline = args.offline;
 
-    let day = cwd
-        .iter()
-        .last()
-        .context("no last segment of cwd")?
-        .to_str()
-        .context("invalid UTF-8")?
-        .strip_prefix("day")
-        .context("cwd does not start with `day`")?
-        .parse::<u8>()?;
-
-    let year = what_year_is_it();
-
-    let cookie = include_str!("../session").trim();
-    let client = Client::new();
-
-    let http_get = |url: &str| -> Result<String> {
-        client
-            .get(url)
-            .header("Cookie", cookie)
-            .header("User-Agent", "The0x539's AoC scraper")
-            .send()
-            .context("transport error")?
-            .error_for_status()
-            .context("http error")?
-            .text()
-            .context("body error")
+    let http = if args.offline {
+        None
+    } else {
+        Some(args.site.connect()?)
     };
+    let http_get = |path: &str| http.as_ref().unwrap().get(path);
 
-    let base_url = format!("https://adventofcode.com/{year}/day/{day}");
-    let html = http_get(&base_url)?;
+    // the cached page predates solving part 1, so updating needs a fresh one
+    cache.refresh = args.refresh || (args.update && !args.offline);
+    let html = cache.get(puzzle, "puzzle.html", || http_get(&puzzle.path()))?;
+    cache.refresh = args.refresh;
 
-    let document = Html::parse_document(&html);
+    if args.update {
+        let real_input = std::fs::read_to_string(out.join("input.txt"))
+            .ok()
+            .filter(|input| !sync::is_placeholder(input));
+        return update(&html, out, real_input.as_deref(), args).map(|()| vec![]);
+    }
+
+    let input_path = format!("{}/input", puzzle.path());
+    let real_input = match cache.get(puzzle, "input.txt", || http_get(&input_path)) {
+        Ok(input) => Some(input),
+        // offline, the input only helps to pick the example
+        Err(_) if args.offline => None,
+        Err(e) => return Err(e.context("input get fail")),
+    };
 
+    let document = Html::parse_document(&html);
     let desc_selector = sel(".day-desc");
     let mut parts = document.select(&desc_selector);
 
     let part1 = parts.next().context("no part 1 description")?;
 
     let blocks = part1.select(&sel("pre > code")).collect::<Vec<_>>();
-    let test_input = pick_block(blocks)?.inner_html();
+    let test_input = examples::text(pick_block(
+        blocks,
+        real_input.as_deref(),
+        args.non_interactive || missing_only,
+    )?);
 
-    let test_output_1 = get_test_output(part1).context("could not find part 1 test output")?;
+    let test_output_1 = examples::answer(part1).context("could not find part 1 test output")?;
 
     let test_output_2 = if let Some(part2) = parts.next() {
-        get_test_output(part2).context("could not find part 2 test output")?
+        examples::answer(part2).context("could not find part 2 test output")?
     } else {
         "0".to_owned()
     };
@@ -115,11 +440,76 @@ fn main() -> Result<()> {
 
     println!("expected test output: {test_output_1} {test_output_2}");
 
-    let real_input = http_get(&(base_url + "/input")).context("input get fail")?;
+    // nothing is written until the page has turned out to be a puzzle
+    let readme = markdown::render(document.select(&desc_selector), &args.site.base_url);
+    let mut files = vec![
+        ("README.md", readme),
+        ("test.txt", test_input),
+        ("test.out.txt", test_output),
+    ];
+    if let Some(real_input) = real_input.filter(|_| !args.offline) {
+        files.push(("input.txt", real_input));
+    }
+    write_files(out, files, missing_only)
+}
 
-    std::fs::write(cwd.join("test.txt"), test_input)?;
-    std::fs::write(cwd.join("test.out.txt"), test_output)?;
-    std::fs::write(cwd.join("input.txt"), real_input)?;
+/// Writes each file under `out` unless it already says the same, or, with `missing_only`,
+/// exists at all as more than a placeholder. Returns the names of the files written.
+fn write_files(
+    out: &Path,
+    files: Vec<(&'static str, String)>,
+    missing_only: bool,
+) -> Result<Vec<&'static str>> {
+    let mut written = vec![];
+    for (name, text) in files {
+        let path = out.join(name);
+        match std::fs::read_to_string(&path) {
+            Ok(old) if missing_only && !sync::is_placeholder(&old) => continue,
+            Ok(old) if old == text => continue,
+            _ => {}
+        }
+        std::fs::write(&path, text)
+            .with_context(|| format!("could not write {}", path.display()))?;
+        written.push(name);
+    }
+    Ok(written)
+}
+
+fn update(html: &str, out: &Path, real_input: Option<&str>, args: &Args) -> Result<()> {
+    let document = Html::parse_document(html);
+    document
+        .select(&sel(".day-desc"))
+        .next()
+        .context("no part 1 description")?;
+    let readme = markdown::render(document.select(&sel(".day-desc")), &args.site.base_url);
+    write_files(out, vec![("README.md", readme)], false)?;
+    let part2 = document
+        .select(&sel(".day-desc"))
+        .nth(1)
+        .context("part 2 is not unlocked yet; solve part 1 first")?;
 
+    let answer = examples::answer(part2).context("could not find part 2 test output")?;
+    let output_path = out.join("test.out.txt");
+    let outputs = std::fs::read_to_string(&output_path).with_context(|| {
+        format!(
+            "could not read {}; fetch part 1 first",
+            output_path.display()
+        )
+    })?;
+    let answer_1 = outputs.lines().next().unwrap_or_default();
+    println!("expected test output: {answer_1} {answer}");
+    std::fs::write(&output_path, format!("{answer_1}\n{answer}"))?;
+
+    // blocks that only repeat part 1's example aren't new
+    let test_input = std::fs::read_to_string(out.join("test.txt")).unwrap_or_default();
+    let blocks = part2
+        .select(&sel("pre > code"))
+        .filter(|&b| examples::text(b).trim() != test_input.trim())
+        .collect::<Vec<_>>();
+    if !blocks.is_empty() {
+        let block = pick_block(blocks, real_input, args.non_interactive)?;
+        std::fs::write(out.join("test2.txt"), examples::text(block))?;
+        println!("wrote part 2's example to test2.txt");
+    }
     Ok(())
 }
@@ -0,0 +1,134 @@
+//! The puzzle description as Markdown, for reading it offline as the day's `README.md`.
+
+use scraper::node::Node;
+use scraper::ElementRef;
+
+use crate::examples;
+
+/// Escapes the characters Markdown would otherwise read as formatting.
+fn escape(text: &str, out: &mut String) {
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Renders inline content, collapsing whitespace as a browser would.
+fn inline(node: ego_tree::NodeRef<Node>, base_url: &str, out: &mut String) {
+    let children = |out: &mut String| {
+        for child in node.children() {
+            inline(child, base_url, out);
+        }
+    };
+    match node.value() {
+        Node::Text(text) => {
+            let words = text.split_whitespace().collect::<Vec<_>>();
+            if text.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+            escape(&words.join(" "), out);
+            if text.ends_with(char::is_whitespace) && !words.is_empty() {
+                out.push(' ');
+            }
+        }
+        Node::Element(element) => match element.name() {
+            "code" => {
+                let code = ElementRef::wrap(node).unwrap().text().collect::<String>();
+                let emphasized = node
+                    .descendants()
+                    .any(|n| n.value().as_element().is_some_and(|e| e.name() == "em"));
+                match emphasized {
+                    true => *out += &format!("*`{code}`*"),
+                    false => *out += &format!("`{code}`"),
+                }
+            }
+            "em" => {
+                out.push('*');
+                children(out);
+                out.push('*');
+            }
+            "a" => {
+                let href = element.attr("href").unwrap_or_default();
+                let href = match href.starts_with('/') {
+                    true => format!("{base_url}{href}"),
+                    false => href.to_owned(),
+                };
+                out.push('[');
+                children(out);
+                *out += &format!("]({href})");
+            }
+            "br" => out.push('\n'),
+            _ => children(out),
+        },
+        _ => {}
+    }
+}
+
+fn inline_text(node: ego_tree::NodeRef<Node>, base_url: &str) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        inline(child, base_url, &mut out);
+    }
+    out.trim().to_owned()
+}
+
+fn block(node: ego_tree::NodeRef<Node>, base_url: &str, out: &mut String) {
+    let Some(element) = ElementRef::wrap(node) else {
+        let mut text = String::new();
+        inline(node, base_url, &mut text);
+        if !text.trim().is_empty() {
+            *out += &format!("{}\n\n", text.trim());
+        }
+        return;
+    };
+
+    match element.value().name() {
+        "h2" => {
+            let title = element.text().collect::<String>();
+            let title = title.trim_matches(|c: char| c == '-' || c.is_whitespace());
+            *out += "## ";
+            escape(title, out);
+            *out += "\n\n";
+        }
+        "pre" => {
+            let code = examples::text(element);
+            *out += &format!("```\n{}\n```\n\n", code.trim_end_matches('\n'));
+        }
+        "ul" | "ol" => {
+            for (i, item) in element.children().filter_map(ElementRef::wrap).enumerate() {
+                let bullet = match element.value().name() {
+                    "ol" => format!("{}.", i + 1),
+                    _ => "-".to_owned(),
+                };
+                *out += &format!("{bullet} {}\n", inline_text(*item, base_url));
+            }
+            *out += "\n";
+        }
+        _ => *out += &format!("{}\n\n", inline_text(node, base_url)),
+    }
+}
+
+/// Renders the description's parts, one after the other. The same page always gives the
+/// same text.
+pub fn render<'a>(parts: impl IntoIterator<Item = ElementRef<'a>>, base_url: &str) -> String {
+    let mut out = String::new();
+    for part in parts {
+        for child in part.children() {
+            block(child, base_url, &mut out);
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+#[cfg(test)]
+#[test]
+fn test_render() {
+    let html = scraper::Html::parse_fragment(include_str!("../fixtures/markdown.html"));
+    let desc = crate::sel(".day-desc");
+    assert_eq!(
+        render(html.select(&desc), "https://adventofcode.com"),
+        include_str!("../fixtures/markdown.md")
+    );
+}
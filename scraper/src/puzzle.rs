@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::prelude::*;
+
+/// Puzzles unlock at midnight US Eastern, which is always UTC-5 in December.
+fn eastern() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).unwrap()
+}
+
+/// The latest year with puzzles out.
+pub fn what_year_is_it() -> u16 {
+    let date = Utc::now().with_timezone(&eastern());
+    if date.month() == 12 {
+        date.year() as u16
+    } else {
+        // grab from last year
+        date.year() as u16 - 1
+    }
+}
+
+/// The year and day a `YYYY/dayNN` directory is for. There is no year for a `dayNN`
+/// directory outside that layout.
+pub fn infer(dir: &Path) -> Result<(Option<u16>, u8)> {
+    let day = dir
+        .file_name()
+        .context("no last segment of directory")?
+        .to_str()
+        .context("invalid UTF-8")?
+        .strip_prefix("day")
+        .context("directory name does not start with `day`")?
+        .parse::<u8>()?;
+
+    let year = dir
+        .parent()
+        .and_then(|p| p.file_name()?.to_str()?.parse().ok());
+
+    Ok((year, day))
+}
+
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    pub fn unlocks_at(self) -> DateTime<FixedOffset> {
+        eastern()
+            .with_ymd_and_hms(self.year.into(), 12, self.day.into(), 0, 0, 0)
+            .unwrap()
+    }
+
+    /// Checks that the puzzle exists, whether or not it is out yet.
+    pub fn check(self) -> Result<()> {
+        if !(1..=25).contains(&self.day) {
+            bail!("day {} is not in 1..=25", self.day);
+        }
+        if self.year < 2015 {
+            bail!("Advent of Code started in 2015, not {}", self.year);
+        }
+        Ok(())
+    }
+
+    /// Checks that the puzzle exists and is out as of `now`.
+    pub fn validate(self, now: DateTime<Utc>) -> Result<()> {
+        self.check()?;
+        let unlock = self.unlocks_at();
+        if now < unlock {
+            bail!(
+                "{self} unlocks at {unlock}, in {}",
+                until(unlock - now.fixed_offset())
+            );
+        }
+        Ok(())
+    }
+
+    /// The puzzle page's path on the site.
+    pub fn path(self) -> String {
+        format!("/{}/day/{}", self.year, self.day)
+    }
+
+    /// Where this puzzle's crate lives, relative to the workspace root.
+    pub fn dir(self) -> PathBuf {
+        format!("{}/day{:02}", self.year, self.day).into()
+    }
+}
+
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// A rough duration, like `3d 4h` or `12m 5s`.
+pub fn until(d: chrono::Duration) -> String {
+    let (days, hours, mins) = (d.num_days(), d.num_hours() % 24, d.num_minutes() % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{}m {}s", mins, d.num_seconds() % 60)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_validate() {
+    let now = Utc.with_ymd_and_hms(2023, 12, 5, 4, 59, 59).unwrap();
+    assert!(Puzzle { year: 2023, day: 4 }.validate(now).is_ok());
+    assert!(Puzzle { year: 2023, day: 5 }.validate(now).is_err());
+    assert!(Puzzle {
+        year: 2023,
+        day: 26
+    }
+    .validate(now)
+    .is_err());
+    assert!(Puzzle { year: 2014, day: 1 }.validate(now).is_err());
+
+    let now = now + chrono::Duration::seconds(1);
+    assert!(Puzzle { year: 2023, day: 5 }.validate(now).is_ok());
+}
@@ -0,0 +1,188 @@
+//! Recorded requests, for exercising the scraper without the site.
+//!
+//! `--record <dir>` saves every request the scraper makes, and the response as it came back
+//! (status, final URL and body), as one JSON file per request in `dir`. `--replay <dir>`
+//! answers requests from those files instead of the site. Replayed responses go through the
+//! same [`http::interpret`] as live ones, so logged-out, locked and missing pages are detected
+//! offline too. Requests that never got a response replay as the same error.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::http::{self, Backend, Raw};
+
+#[derive(Serialize, Deserialize)]
+struct Exchange {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    form: Vec<(String, String)>,
+    #[serde(flatten)]
+    result: Recorded,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Recorded {
+    Response(Raw),
+    /// The transport error, as it was reported.
+    Error(String),
+}
+
+/// The fixture file name for a request, e.g. `post-2023-day-5-answer-level=1-answer=42.json`.
+fn file_name(method: &str, path: &str, form: &[(&str, &str)]) -> String {
+    let mut key = method.to_lowercase() + path;
+    for (k, v) in form {
+        key += &format!("-{k}={v}");
+    }
+    let key = key
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() || c == '=' || c == '.' => c,
+            _ => '-',
+        })
+        .collect::<String>();
+    format!("{key}.json")
+}
+
+/// Saves an exchange in `dir`, overwriting any earlier recording of the same request.
+pub fn record(
+    dir: &Path,
+    method: &str,
+    path: &str,
+    form: &[(&str, &str)],
+    result: &Result<Raw>,
+) -> Result<()> {
+    let exchange = Exchange {
+        method: method.to_owned(),
+        path: path.to_owned(),
+        form: form.iter().map(|&(k, v)| (k.into(), v.into())).collect(),
+        result: match result {
+            Ok(raw) => Recorded::Response(raw.clone()),
+            Err(e) => Recorded::Error(format!("{e:#}")),
+        },
+    };
+    let file = dir.join(file_name(method, path, form));
+    std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&file, serde_json::to_string_pretty(&exchange)?))
+        .with_context(|| format!("could not write {}", file.display()))
+}
+
+/// Answers requests from a directory of recorded exchanges.
+pub struct Replay {
+    dir: PathBuf,
+}
+
+impl Replay {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn replay(&self, method: &str, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        let file = self.dir.join(file_name(method, path, form));
+        let text = std::fs::read_to_string(&file)
+            .with_context(|| format!("no recording of {method} {path} at {}", file.display()))?;
+        let exchange = serde_json::from_str::<Exchange>(&text)
+            .with_context(|| format!("malformed recording {}", file.display()))?;
+        match exchange.result {
+            Recorded::Response(raw) => http::interpret(raw),
+            Recorded::Error(message) => Err(anyhow!(message)),
+        }
+    }
+}
+
+impl Backend for Replay {
+    fn get(&self, path: &str) -> Result<String> {
+        self.replay("GET", path, &[])
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.replay("POST", path, form)
+    }
+}
+
+#[cfg(test)]
+fn fixtures() -> Replay {
+    Replay::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/replay").into())
+}
+
+#[cfg(test)]
+#[test]
+fn test_replay_puzzle() {
+    use crate::http::Refusal;
+    use crate::{examples, sel};
+
+    let replay = fixtures();
+    let html = scraper::Html::parse_document(&replay.get("/2023/day/3").unwrap());
+    let part1 = html.select(&sel(".day-desc")).next().unwrap();
+    let block = part1.select(&sel("pre > code")).next().unwrap();
+    assert_eq!(examples::text(block), "467..114..\n...*......\n");
+    assert_eq!(examples::answer(part1).as_deref(), Some("4361"));
+
+    assert_eq!(replay.get("/2023/day/3/input").unwrap(), "1.3*..\n..45*.\n");
+
+    let refusal = |path| {
+        let e = replay.get(path).unwrap_err();
+        e.downcast_ref::<Refusal>()
+            .unwrap_or_else(|| panic!("{e:#}"))
+            .to_string()
+    };
+    assert_eq!(refusal("/2023/day/4"), Refusal::NotFound.to_string());
+    assert_eq!(refusal("/2022/day/25"), Refusal::Locked.to_string());
+    assert_eq!(refusal("/2023/day/7"), Refusal::LoggedOut.to_string());
+    assert_eq!(refusal("/2023/day/8/input"), Refusal::LoggedOut.to_string());
+    let missing = replay.get("/2023/day/6").unwrap_err().to_string();
+    assert!(
+        missing.starts_with("no recording of GET /2023/day/6"),
+        "{missing}"
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_replay_submit() {
+    use crate::puzzle::Puzzle;
+    use crate::submit::{submit, Outcome};
+
+    let replay = fixtures();
+    let puzzle = Puzzle { year: 2023, day: 5 };
+    assert_eq!(submit(&replay, puzzle, 1, "42").unwrap(), Outcome::Correct);
+    assert_eq!(submit(&replay, puzzle, 2, "7").unwrap(), Outcome::TooLow);
+}
+
+#[cfg(test)]
+#[test]
+fn test_record() {
+    let dir = crate::test_dir::TestDir::new("record");
+    let replay = Replay::new(dir.to_path_buf());
+
+    let form = [("level", "1"), ("answer", "42")];
+    let raw = Raw {
+        status: 200,
+        url: "https://adventofcode.com/2023/day/5/answer".into(),
+        body: "<p>That's the right answer!</p>".into(),
+    };
+    record(&dir, "POST", "/2023/day/5/answer", &form, &Ok(raw)).unwrap();
+    assert_eq!(
+        replay.post("/2023/day/5/answer", &form).unwrap(),
+        "<p>That's the right answer!</p>"
+    );
+
+    let raw = Raw {
+        status: 503,
+        url: "https://adventofcode.com/2023/day/5".into(),
+        body: String::new(),
+    };
+    record(&dir, "GET", "/2023/day/5", &[], &Ok(raw)).unwrap();
+    let error = replay.get("/2023/day/5").unwrap_err().to_string();
+    assert!(error.starts_with("http error: 503"), "{error}");
+
+    let error = anyhow!("transport error: connection refused");
+    record(&dir, "GET", "/2023/day/5/input", &[], &Err(error)).unwrap();
+    assert_eq!(
+        replay.get("/2023/day/5/input").unwrap_err().to_string(),
+        "transport error: connection refused"
+    );
+}
@@ -0,0 +1,140 @@
+//! `scraper new-day`: a day crate copied from `template/`.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::puzzle::Puzzle;
+use crate::sync;
+
+/// The lines of `lines` inside the workspace members list.
+fn members_range(lines: &[&str]) -> Result<Range<usize>> {
+    let start = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("members = ["))
+        .context("no members list in Cargo.toml")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "]")
+            .context("members list in Cargo.toml is not closed")?;
+    Ok(start + 1..end)
+}
+
+/// The workspace members listed in `manifest`, as written there.
+pub fn members(manifest: &str) -> Result<Vec<&str>> {
+    let lines = manifest.lines().collect::<Vec<_>>();
+    Ok(lines[members_range(&lines)?]
+        .iter()
+        .map(|l| l.trim().trim_end_matches(',').trim_matches('"'))
+        .filter(|m| !m.is_empty())
+        .collect())
+}
+
+/// `manifest` with `member` added to the workspace members, among the other days in sorted
+/// order, or after the last member if there are no days yet.
+fn insert_member(manifest: &str, member: &str) -> Result<String> {
+    let line = format!("  \"{member}\",");
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let range = members_range(&lines)?;
+
+    let is_day = |l: &&str| l.trim().trim_matches(['"', ',']).contains("/day");
+    if lines[range.clone()].iter().any(|l| l.trim() == line.trim()) {
+        bail!("{member} is already a workspace member");
+    }
+    let at = lines[range.clone()]
+        .iter()
+        .position(|l| is_day(l) && l.trim() > line.trim())
+        .map_or(range.end, |i| range.start + i);
+    lines.insert(at, &line);
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Ok(out)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the puzzle's crate under `root` and adds it to the workspace. Returns its
+/// directory.
+pub fn new_day(root: &Path, puzzle: Puzzle) -> Result<PathBuf> {
+    let dir = root.join(puzzle.dir());
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+
+    let member = puzzle.dir().to_str().unwrap().replace('\\', "/");
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).context("could not read Cargo.toml")?;
+    let manifest = insert_member(&manifest, &member)?;
+
+    let template = std::fs::read_to_string(root.join("template/Cargo.toml"))
+        .context("could not read template/Cargo.toml")?;
+    let package = format!("name = \"y{}-day{:02}\"", puzzle.year, puzzle.day);
+    if !template.contains("name = \"template\"") {
+        bail!("template/Cargo.toml does not name the package `template`");
+    }
+    let day_manifest = template.replacen("name = \"template\"", &package, 1);
+
+    // the day needs its input and test files to compile, so it gets the template's
+    // placeholders (or blank ones); `fetch` and `sync` see those as missing
+    let template_dir = root.join("template");
+    copy_dir(&template_dir.join("src"), &dir.join("src"))
+        .and_then(|()| Ok(std::fs::write(dir.join("Cargo.toml"), day_manifest)?))
+        .and_then(|()| {
+            for file in sync::FILES {
+                let placeholder = template_dir.join(file);
+                if placeholder.exists() {
+                    std::fs::copy(&placeholder, dir.join(file))?;
+                } else {
+                    std::fs::write(dir.join(file), "\n")?;
+                }
+            }
+            Ok(())
+        })
+        .inspect_err(|_| {
+            let _ = std::fs::remove_dir_all(&dir);
+        })
+        .with_context(|| format!("could not create {}", dir.display()))?;
+    std::fs::write(&manifest_path, manifest).context("could not write Cargo.toml")?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+#[test]
+fn test_insert_member() {
+    let manifest =
+        "[workspace]\nmembers = [\n  \"util\",\n  \"2023/day01\",\n  \"2023/day03\",\n]\n";
+    assert_eq!(
+        insert_member(manifest, "2023/day02").unwrap(),
+        "[workspace]\nmembers = [\n  \"util\",\n  \"2023/day01\",\n  \"2023/day02\",\n  \"2023/day03\",\n]\n"
+    );
+    assert_eq!(
+        insert_member(manifest, "2024/day01").unwrap(),
+        "[workspace]\nmembers = [\n  \"util\",\n  \"2023/day01\",\n  \"2023/day03\",\n  \"2024/day01\",\n]\n"
+    );
+    assert!(insert_member(manifest, "2023/day03").is_err());
+    assert_eq!(
+        members(manifest).unwrap(),
+        ["util", "2023/day01", "2023/day03"]
+    );
+
+    let empty = "[workspace]\nmembers = [\n  \"util\",\n]\n";
+    assert_eq!(
+        insert_member(empty, "2023/day01").unwrap(),
+        "[workspace]\nmembers = [\n  \"util\",\n  \"2023/day01\",\n]\n"
+    );
+}
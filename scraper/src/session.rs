@@ -0,0 +1,72 @@
+//! Where the session cookie comes from.
+//!
+//! The token is the value of the `session` cookie on adventofcode.com. It is looked up in
+//! `AOC_SESSION`, then in `session` under the config directory (`$XDG_CONFIG_HOME/aoc`,
+//! `~/.config/aoc`, or `%APPDATA%\aoc`), then in `--session`.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The directory holding the scraper's configuration.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if cfg!(windows) => PathBuf::from(std::env::var_os("APPDATA")?),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("aoc"))
+}
+
+/// Checks the shape of a token, tolerating a pasted `session=` prefix and whitespace.
+fn parse(raw: &str, source: &str) -> Result<String> {
+    let token = raw.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() {
+        bail!("the session token in {source} is empty");
+    }
+    if !token.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("the session token in {source} is malformed: expected only hex digits, as in the `session` cookie");
+    }
+    Ok(token.to_owned())
+}
+
+/// The token to send, from the first source that has one.
+pub fn load(flag: Option<&str>) -> Result<String> {
+    if let Ok(token) = std::env::var(SESSION_VAR) {
+        return parse(&token, SESSION_VAR);
+    }
+
+    if let Some(path) = config_dir().map(|dir| dir.join("session")) {
+        match std::fs::read_to_string(&path) {
+            Ok(token) => return parse(&token, &path.display().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+        }
+    }
+
+    if let Some(token) = flag {
+        return parse(token, "--session");
+    }
+
+    let path = config_dir().map_or("<config dir>/session".into(), |d| {
+        d.join("session").display().to_string()
+    });
+    bail!(
+        "no session token: set {SESSION_VAR}, write it to {path}, or pass --session \
+         (it is the `session` cookie on adventofcode.com once logged in)"
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse() {
+    assert_eq!(
+        parse(" session=53616c7465645f5f\n", "test").unwrap(),
+        "53616c7465645f5f"
+    );
+    assert!(parse("", "test").is_err());
+    assert!(parse("not a token", "test").is_err());
+}
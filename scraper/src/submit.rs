@@ -0,0 +1,185 @@
+//! `scraper submit`: posting an answer and reading the verdict.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use scraper::Html;
+
+use crate::http::Backend;
+use crate::puzzle::Puzzle;
+use crate::sel;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too soon after a previous attempt; the page says how long is left, if it
+    /// could be read.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or part 2 is not unlocked yet.
+    WrongLevel,
+    /// A page none of the above matched, as text.
+    Unknown(String),
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong: too high"),
+            Self::TooLow => write!(f, "wrong: too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(Some(wait)) => write!(f, "rate limited: {wait:?} left to wait"),
+            Self::RateLimited(None) => write!(f, "rate limited"),
+            Self::WrongLevel => write!(f, "not the right level: already solved, or still locked"),
+            Self::Unknown(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+impl Outcome {
+    /// How the outcome is written in the submission history. Details of rate limits and
+    /// unrecognized pages are not kept.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::Wrong => "wrong",
+            Self::RateLimited(_) => "rate_limited",
+            Self::WrongLevel => "wrong_level",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Some(match tag {
+            "correct" => Self::Correct,
+            "too_high" => Self::TooHigh,
+            "too_low" => Self::TooLow,
+            "wrong" => Self::Wrong,
+            "rate_limited" => Self::RateLimited(None),
+            "wrong_level" => Self::WrongLevel,
+            "unknown" => Self::Unknown(String::new()),
+            _ => return None,
+        })
+    }
+
+    /// Whether the site judged the answer and said it was wrong.
+    pub fn rejected(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+/// A wait like `1m 23s` from "You have 1m 23s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut secs = 0;
+    for token in wait.split_whitespace() {
+        let unit = token.chars().last()?;
+        let n = token[..token.len() - 1].parse::<u64>().ok()?;
+        secs += n * match unit {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Reads the verdict off the page the site shows after a submission.
+pub fn parse_response(html: &str) -> Outcome {
+    let document = Html::parse_document(html);
+    let text = match document.select(&sel("main article")).next() {
+        Some(article) => article.text().collect::<String>(),
+        None => document.root_element().text().collect(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text))
+    } else if text.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if text.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+pub fn submit(http: &dyn Backend, puzzle: Puzzle, part: u8, answer: &str) -> Result<Outcome> {
+    let level = part.to_string();
+    let form = [("level", level.as_str()), ("answer", answer)];
+    let html = http.post(&format!("{}/answer", puzzle.path()), &form)?;
+    Ok(parse_response(&html))
+}
+
+/// Builds and runs the day's binary on its input, and takes the answer for `part` from its
+/// output.
+pub fn solve(root: &Path, puzzle: Puzzle, part: u8) -> Result<String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let package = format!("y{}-day{:02}", puzzle.year, puzzle.day);
+    let output = Command::new(cargo)
+        .current_dir(root)
+        .args(["run", "--release", "--quiet", "-p", &package])
+        .env("AOC_JSON", "0")
+        .output()
+        .context("could not run cargo")?;
+    if !output.status.success() {
+        bail!(
+            "{package} failed ({}):\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout).context("answer is not UTF-8")?;
+    let answer = stdout
+        .lines()
+        .nth(usize::from(part) - 1)
+        .with_context(|| format!("{package} printed no answer for part {part}"))?;
+    Ok(answer.trim().to_owned())
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_response() {
+    let page =
+        |p: &str| format!("<html><body><main><article><p>{p}</p></article></main></body></html>");
+
+    let correct =
+        page("That's the right answer! You are one gold star closer to saving the world.");
+    assert_eq!(parse_response(&correct), Outcome::Correct);
+
+    let high = page("That's not the right answer; your answer is too high. Please wait one minute before trying again.");
+    assert_eq!(parse_response(&high), Outcome::TooHigh);
+
+    let low = page("That's not the right answer; your answer is too low.");
+    assert_eq!(parse_response(&low), Outcome::TooLow);
+
+    let wrong = page(
+        "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+    );
+    assert_eq!(parse_response(&wrong), Outcome::Wrong);
+
+    let limited = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a>");
+    assert_eq!(
+        parse_response(&limited),
+        Outcome::RateLimited(Some(Duration::from_secs(83)))
+    );
+
+    let level = page("You don't seem to be solving the right level.  Did you already complete it?");
+    assert_eq!(parse_response(&level), Outcome::WrongLevel);
+}
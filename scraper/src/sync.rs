@@ -0,0 +1,157 @@
+//! `scraper sync`: the missing inputs and examples of every day in the workspace.
+//!
+//! Days with all of [`FILES`] are left alone without a request, and pages already in the cache
+//! are not fetched again, so running it twice costs nothing. A blank file, like the placeholders
+//! a new day gets so that it compiles, counts as missing. Requests go through the usual
+//! throttling. Once the site says the session is no good, the remaining days are skipped.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::http::Refusal;
+use crate::puzzle::{self, Puzzle};
+use crate::scaffold;
+
+/// The files a day needs to run and test.
+pub const FILES: [&str; 3] = ["input.txt", "test.txt", "test.out.txt"];
+
+pub struct Day {
+    pub puzzle: Puzzle,
+    pub dir: PathBuf,
+}
+
+pub enum Status {
+    UpToDate,
+    /// The files that were written.
+    Fetched(Vec<&'static str>),
+    Locked,
+    Failed(String),
+    /// Not tried, because an earlier day found the session logged out.
+    Skipped,
+}
+
+/// The workspace members under `root` that are days, in manifest order.
+pub fn days(root: &Path) -> Result<Vec<Day>> {
+    let manifest =
+        std::fs::read_to_string(root.join("Cargo.toml")).context("could not read Cargo.toml")?;
+    Ok(scaffold::members(&manifest)?
+        .into_iter()
+        .filter_map(|member| {
+            let dir = root.join(member);
+            let (year, day) = puzzle::infer(&dir).ok()?;
+            let puzzle = Puzzle { year: year?, day };
+            puzzle.check().ok()?;
+            Some(Day { puzzle, dir })
+        })
+        .collect())
+}
+
+/// Whether a day's file is only a placeholder, and so as good as missing.
+pub fn is_placeholder(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+fn missing(dir: &Path) -> Vec<&'static str> {
+    FILES
+        .into_iter()
+        .filter(|f| std::fs::read_to_string(dir.join(f)).map_or(true, |text| is_placeholder(&text)))
+        .collect()
+}
+
+/// Runs `fetch` for each day missing some of [`FILES`] that has unlocked as of `now`.
+/// `fetch` returns the names of the files it wrote.
+pub fn sync(
+    days: &[Day],
+    now: DateTime<Utc>,
+    mut fetch: impl FnMut(&Day) -> Result<Vec<&'static str>>,
+) -> Vec<Status> {
+    let mut logged_out = false;
+    days.iter()
+        .map(|day| {
+            if missing(&day.dir).is_empty() {
+                Status::UpToDate
+            } else if day.puzzle.validate(now).is_err() {
+                Status::Locked
+            } else if logged_out {
+                Status::Skipped
+            } else {
+                match fetch(day) {
+                    Ok(written) => Status::Fetched(written),
+                    Err(e) => match e.downcast_ref::<Refusal>() {
+                        Some(Refusal::Locked) => Status::Locked,
+                        refusal => {
+                            logged_out = refusal == Some(&Refusal::LoggedOut);
+                            Status::Failed(format!("{e:#}"))
+                        }
+                    },
+                }
+            }
+        })
+        .collect()
+}
+
+/// One line per day: its directory relative to `root`, then how it went.
+pub fn render(root: &Path, days: &[Day], statuses: &[Status]) -> String {
+    let mut out = String::new();
+    for (day, status) in days.iter().zip(statuses) {
+        let dir = day.dir.strip_prefix(root).unwrap_or(&day.dir);
+        let status = match status {
+            Status::UpToDate => "up to date".to_owned(),
+            Status::Fetched(written) if written.is_empty() => "nothing new".to_owned(),
+            Status::Fetched(written) => format!("wrote {}", written.join(", ")),
+            Status::Locked => "locked".to_owned(),
+            Status::Failed(e) => format!("failed: {e}"),
+            Status::Skipped => "skipped".to_owned(),
+        };
+        let dir = dir.to_string_lossy().replace('\\', "/");
+        writeln!(out, "{dir:<12} {status}").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+#[test]
+fn test_sync() {
+    use chrono::TimeZone;
+
+    let root = crate::test_dir::TestDir::new("sync");
+    let day = |day| {
+        let dir = root.join(format!("2023/day{day:02}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        Day {
+            puzzle: Puzzle { year: 2023, day },
+            dir,
+        }
+    };
+    let days = [day(1), day(2), day(3), day(4), day(5), day(6)];
+    for f in FILES {
+        std::fs::write(days[0].dir.join(f), "1\n").unwrap();
+        std::fs::write(days[1].dir.join(f), "\n").unwrap();
+    }
+    std::fs::write(days[1].dir.join("input.txt"), "2\n").unwrap();
+
+    let now = Utc.with_ymd_and_hms(2023, 12, 5, 12, 0, 0).unwrap();
+    let mut fetched = vec![];
+    let statuses = sync(&days, now, |day| {
+        fetched.push(day.puzzle.day);
+        match day.puzzle.day {
+            2 => Ok(vec!["test.txt", "test.out.txt"]),
+            3 => Err(anyhow::Error::from(Refusal::NotFound).context("input get fail")),
+            _ => Err(Refusal::LoggedOut.into()),
+        }
+    });
+    assert_eq!(fetched, [2, 3, 4]);
+    assert_eq!(
+        render(&root, &days, &statuses),
+        "2023/day01   up to date\n\
+         2023/day02   wrote test.txt, test.out.txt\n\
+         2023/day03   failed: input get fail: the site has no such page (404 Not Found)\n\
+         2023/day04   failed: adventofcode.com rejected the session token; it has probably \
+         expired, so log in again and copy the new `session` cookie\n\
+         2023/day05   skipped\n\
+         2023/day06   locked\n"
+    );
+}
@@ -0,0 +1,31 @@
+//! Scratch directories for tests.
+
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system temp dir, removed when dropped, even if the test
+/// panics.
+pub struct TestDir(PathBuf);
+
+impl TestDir {
+    /// `name` keeps the directories of tests running at the same time apart.
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl std::ops::Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,104 @@
+//! `--wait`: sleeping until a puzzle unlocks.
+
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::puzzle::{self, Puzzle};
+
+/// How long after the unlock to fetch, so the site has surely caught up.
+pub const MARGIN: Duration = Duration::from_secs(5);
+
+/// Time as the waiting sees it, so tests don't have to actually wait.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Sleeps until [`MARGIN`] after `puzzle` unlocks. With `redraw`, a countdown on `out` is
+/// redrawn in place every second; otherwise it is written once.
+pub fn until_unlocked(
+    puzzle: Puzzle,
+    clock: &impl Clock,
+    out: &mut impl Write,
+    redraw: bool,
+) -> std::io::Result<()> {
+    let target = puzzle.unlocks_at() + chrono::Duration::from_std(MARGIN).unwrap();
+    let mut drawn = false;
+    while let Ok(left) = (target - clock.now().fixed_offset()).to_std() {
+        if left.is_zero() {
+            break;
+        }
+        let countdown = puzzle::until(chrono::Duration::from_std(left).unwrap());
+        if redraw {
+            write!(out, "\r\x1b[K{puzzle} unlocks in {countdown}")?;
+            out.flush()?;
+            drawn = true;
+            clock.sleep(left.min(Duration::from_secs(1)));
+        } else {
+            writeln!(out, "waiting {countdown} for {puzzle} to unlock")?;
+            clock.sleep(left);
+        }
+    }
+    if drawn {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_until_unlocked() {
+    use chrono::TimeZone;
+    use std::cell::Cell;
+
+    struct FakeClock(Cell<DateTime<Utc>>);
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+        fn sleep(&self, duration: Duration) {
+            self.0
+                .set(self.0.get() + chrono::Duration::from_std(duration).unwrap());
+        }
+    }
+
+    let puzzle = Puzzle { year: 2023, day: 5 };
+    let unlock = Utc.with_ymd_and_hms(2023, 12, 5, 5, 0, 0).unwrap();
+
+    let clock = FakeClock(Cell::new(unlock - chrono::Duration::seconds(3)));
+    let mut out = vec![];
+    until_unlocked(puzzle, &clock, &mut out, true).unwrap();
+    assert_eq!(clock.now(), unlock + chrono::Duration::seconds(5));
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.matches('\r').count(), 8);
+    assert!(out.starts_with("\r\x1b[K2023 day 5 unlocks in 0m 8s"));
+
+    let clock = FakeClock(Cell::new(unlock - chrono::Duration::hours(2)));
+    let mut out = vec![];
+    until_unlocked(puzzle, &clock, &mut out, false).unwrap();
+    assert_eq!(clock.now(), unlock + chrono::Duration::seconds(5));
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "waiting 2h 0m for 2023 day 5 to unlock\n"
+    );
+
+    let clock = FakeClock(Cell::new(unlock + chrono::Duration::hours(1)));
+    let mut out = vec![];
+    until_unlocked(puzzle, &clock, &mut out, true).unwrap();
+    assert_eq!(clock.now(), unlock + chrono::Duration::hours(1));
+    assert!(out.is_empty());
+}
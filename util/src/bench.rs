@@ -0,0 +1,62 @@
+//! Repeated timing of parsing and both parts, for the runner's `--bench` mode.
+//!
+//! With `AOC_BENCH=runs` set, a day binary parses and solves its input `runs` times instead of
+//! printing answers, then prints one JSON object per phase:
+//!
+//! ```text
+//! {"year":2023,"day":8,"phase":"parse","runs":10,"median_ns":1234567,"min_ns":1200000}
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::report;
+
+pub const BENCH_VAR: &str = "AOC_BENCH";
+
+/// How many runs `AOC_BENCH` asks for, if it is set.
+pub fn runs() -> Option<usize> {
+    let runs = std::env::var(BENCH_VAR).ok()?;
+    let runs = runs
+        .trim()
+        .parse()
+        .expect("AOC_BENCH should be a number of runs");
+    Some(usize::max(runs, 1))
+}
+
+fn time<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let out = black_box(f());
+    samples.push(start.elapsed());
+    out
+}
+
+fn print(package: &str, phase: &str, mut samples: Vec<Duration>) {
+    samples.sort();
+    println!(
+        r#"{{{},"phase":"{phase}","runs":{},"median_ns":{},"min_ns":{}}}"#,
+        report::id_fields(package),
+        samples.len(),
+        samples[samples.len() / 2].as_nanos(),
+        samples[0].as_nanos(),
+    );
+}
+
+pub fn bench<In, Out1, Out2>(
+    package: &str,
+    runs: usize,
+    mut parse: impl FnMut() -> In,
+    mut part1: impl FnMut(&In) -> Out1,
+    mut part2: impl FnMut(&In) -> Out2,
+) {
+    let (mut parse_t, mut part1_t, mut part2_t) = (vec![], vec![], vec![]);
+    for _ in 0..runs {
+        let input = time(&mut parse_t, &mut parse);
+        time(&mut part1_t, || part1(&input));
+        time(&mut part2_t, || part2(&input));
+    }
+
+    print(package, "parse", parse_t);
+    print(package, "part1", part1_t);
+    print(package, "part2", part2_t);
+}
@@ -0,0 +1,182 @@
+//! Differential testing: run a naive and an optimized implementation on random inputs and
+//! report the smallest input they disagree on.
+
+use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::rng::Rng;
+
+/// Produces "smaller" variants of a value, tried in order while shrinking a failing case.
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut v = vec![0, self / 2, self.saturating_sub(1)];
+                v.dedup();
+                v.retain(|x| x < self);
+                v
+            }
+        }
+    )*};
+}
+
+macro_rules! shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut v = vec![0, self / 2, self - self.signum()];
+                if *self < 0 {
+                    v.insert(0, self.wrapping_neg());
+                }
+                v.dedup();
+                v.retain(|x| x != self);
+                v
+            }
+        }
+    )*};
+}
+
+shrink_unsigned!(u8, u16, u32, u64, usize);
+shrink_signed!(i8, i16, i32, i64, isize);
+
+impl<T: Shrink + Clone> Shrink for Option<T> {
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            None => vec![],
+            Some(x) => std::iter::once(None)
+                .chain(x.shrink().into_iter().map(Some))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = vec![];
+        if self.len() > 1 {
+            out.push(self[..self.len() / 2].to_vec());
+            out.push(self[self.len() / 2..].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut v = self.clone();
+            v.remove(i);
+            out.push(v);
+        }
+        for i in 0..self.len() {
+            for x in self[i].shrink() {
+                let mut v = self.clone();
+                v[i] = x;
+                out.push(v);
+            }
+        }
+        out
+    }
+}
+
+impl<T: Shrink + Clone, const N: usize> Shrink for [T; N] {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = vec![];
+        for i in 0..N {
+            for x in self[i].shrink() {
+                let mut v = self.clone();
+                v[i] = x;
+                out.push(v);
+            }
+        }
+        out
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let left = a.shrink().into_iter().map(|a| (a, b.clone()));
+        let right = b.shrink().into_iter().map(|b| (a.clone(), b));
+        left.chain(right).collect()
+    }
+}
+
+/// Outcome of running both implementations on one input.
+enum Trial {
+    /// Both returned the same value, or the reference rejected the input by panicking.
+    Agree,
+    Disagree(String, String),
+}
+
+fn trial<T, O, Naive, Fast>(input: &T, naive: &Naive, fast: &Fast) -> Trial
+where
+    O: PartialEq + Debug,
+    Naive: Fn(&T) -> O,
+    Fast: Fn(&T) -> O,
+{
+    let Ok(expected) = catch_unwind(AssertUnwindSafe(|| naive(input))) else {
+        return Trial::Agree;
+    };
+    match catch_unwind(AssertUnwindSafe(|| fast(input))) {
+        Ok(actual) if actual == expected => Trial::Agree,
+        Ok(actual) => Trial::Disagree(format!("{expected:?}"), format!("{actual:?}")),
+        Err(_) => Trial::Disagree(format!("{expected:?}"), "<panicked>".into()),
+    }
+}
+
+/// Generates `cases` random inputs and checks that `fast` agrees with `naive` on all of them.
+///
+/// On the first disagreement, the input is greedily shrunk using `shrink` and the test panics
+/// with the smallest failing input and the seed needed to reproduce it (via `AOC_SEED`).
+/// Inputs on which `naive` panics are treated as invalid and skipped.
+pub fn check<T, O, Gen, Shr, Naive, Fast>(
+    cases: usize,
+    mut generate: Gen,
+    shrink: Shr,
+    naive: Naive,
+    fast: Fast,
+) where
+    T: Debug,
+    O: PartialEq + Debug,
+    Gen: FnMut(&mut Rng) -> T,
+    Shr: Fn(&T) -> Vec<T>,
+    Naive: Fn(&T) -> O,
+    Fast: Fn(&T) -> O,
+{
+    let seed = Rng::seed_from_env();
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        let Trial::Disagree(mut expected, mut actual) = trial(&input, &naive, &fast) else {
+            continue;
+        };
+
+        let mut input = input;
+        'shrinking: loop {
+            for candidate in shrink(&input) {
+                if let Trial::Disagree(e, a) = trial(&candidate, &naive, &fast) {
+                    (input, expected, actual) = (candidate, e, a);
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+
+        panic!(
+            "implementations disagree (AOC_SEED={seed}, case {case})\n\
+             input: {input:?}\n\
+             naive: {expected}\n\
+             fast:  {actual}"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Random puzzle inputs for stress testing.
+//!
+//! A day opts in with `register!(parse, part1, part2, @gen generate)`, where `generate` builds
+//! an input whose difficulty grows with `size`. The day binary then understands two variables:
+//! `AOC_GENERATE=size[:seed]` prints a generated input instead of solving, and
+//! `AOC_INPUT=path` solves the given file instead of the bundled `input.txt`.
+
+use crate::rng::Rng;
+use crate::Pos;
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub const GENERATE_VAR: &str = "AOC_GENERATE";
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// Exit code of a day binary asked to generate input when it has no generator.
+pub const NO_GENERATOR: i32 = 2;
+
+/// Handles `AOC_GENERATE`. Returns true if an input was printed and the day should not run.
+pub fn generate_from_env(generator: Option<Generator>) -> bool {
+    let Ok(spec) = std::env::var(GENERATE_VAR) else {
+        return false;
+    };
+    let Some(generator) = generator else {
+        eprintln!("this day has no input generator");
+        std::process::exit(NO_GENERATOR);
+    };
+
+    let (size, seed) = match spec.split_once(':') {
+        Some((size, seed)) => (size, seed.parse().expect("invalid seed")),
+        None => (spec.as_str(), 0),
+    };
+    let size = size.parse().expect("invalid size");
+
+    print!("{}", generator(&mut Rng::new(seed), size));
+    true
+}
+
+/// The bundled input, unless `AOC_INPUT` names a file to read instead.
+pub fn input(bundled: &'static str) -> &'static str {
+    match std::env::var(INPUT_VAR) {
+        Ok(path) => std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read {path}: {e}"))
+            .leak(),
+        Err(_) => bundled,
+    }
+}
+
+/// Builds a `w`×`h` character grid, one line per row.
+pub fn grid(rng: &mut Rng, w: usize, h: usize, mut cell: impl FnMut(&mut Rng) -> char) -> String {
+    let mut s = String::with_capacity((w + 1) * h);
+    for _ in 0..h {
+        s.extend((0..w).map(|_| cell(rng)));
+        s.push('\n');
+    }
+    s
+}
+
+/// Grows a random blob of cells from the center of a `w`×`h` grid.
+///
+/// The blob stays 4-connected, has no holes, and never touches itself only at a corner,
+/// so its outline is a single simple loop.
+pub fn blob(rng: &mut Rng, w: usize, h: usize) -> Vec<Vec<bool>> {
+    const RING: [(i64, i64); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+
+    let mut cells = vec![vec![false; w]; h];
+    let get = |cells: &[Vec<bool>], x: i64, y: i64| {
+        (0..w as i64).contains(&x) && (0..h as i64).contains(&y) && cells[y as usize][x as usize]
+    };
+
+    let mut members = vec![(w as i64 / 2, h as i64 / 2)];
+    cells[h / 2][w / 2] = true;
+
+    for _ in 0..w * h * 2 {
+        let (x, y) = *rng.choose(&members);
+        let (dx, dy) = RING[rng.index(4) * 2];
+        let (x, y) = (x + dx, y + dy);
+        if !(0..w as i64).contains(&x) || !(0..h as i64).contains(&y) || get(&cells, x, y) {
+            continue;
+        }
+
+        // adding a cell is safe iff its neighbors in the blob form one unbroken arc
+        // that includes an edge neighbor, not just a corner
+        let ring = RING.map(|(dx, dy)| get(&cells, x + dx, y + dy));
+        let arcs = (0..8).filter(|&i| ring[i] && !ring[(i + 7) % 8]).count();
+        let edge = (0..8).step_by(2).any(|i| ring[i]);
+        if arcs == 1 && edge {
+            cells[y as usize][x as usize] = true;
+            members.push((x, y));
+        }
+    }
+
+    cells
+}
+
+/// Walks the outline of a [`blob`] clockwise (with `y` pointing down), along the corners
+/// between cells. Returns each corner with the unit step taken from it.
+pub fn outline(blob: &[Vec<bool>]) -> Vec<(Pos<i64>, (i64, i64))> {
+    let mut next = std::collections::HashMap::new();
+    let inside = |x: i64, y: i64| {
+        y >= 0
+            && x >= 0
+            && blob
+                .get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+
+    for (y, row) in blob.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|c| *c.1) {
+            let (x, y) = (x as i64, y as i64);
+            if !inside(x, y - 1) {
+                next.insert(Pos::new(x, y), (1, 0));
+            }
+            if !inside(x + 1, y) {
+                next.insert(Pos::new(x + 1, y), (0, 1));
+            }
+            if !inside(x, y + 1) {
+                next.insert(Pos::new(x + 1, y + 1), (-1, 0));
+            }
+            if !inside(x - 1, y) {
+                next.insert(Pos::new(x, y + 1), (0, -1));
+            }
+        }
+    }
+
+    let start = *next.keys().min().unwrap();
+    let mut path = vec![];
+    let mut pos = start;
+    loop {
+        let dir = next[&pos];
+        path.push((pos, dir));
+        pos += dir;
+        if pos == start {
+            return path;
+        }
+    }
+}
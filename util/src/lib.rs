@@ -4,6 +4,15 @@ use std::str::FromStr;
 
 pub use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+pub mod bench;
+pub mod diff;
+pub mod gen;
+pub mod math;
+pub mod progress;
+pub mod report;
+pub mod rng;
+pub mod trace;
+
 pub fn p<T>(s: &str) -> T
 where
     T: FromStr,
@@ -97,43 +106,63 @@ pub fn parse_input_lines<T, F: FnMut(&'static str) -> T>(input_data: &'static st
 }
 
 pub fn run<Parser, Part1, Part2, In, Out>(
+    package: &str,
     input_data: &'static str,
-    parser: Parser,
-    part1: Part1,
-    part2: Part2,
+    mut parser: Parser,
+    mut part1: Part1,
+    mut part2: Part2,
 ) where
     Parser: FnMut(&'static str) -> In,
-    Part1: FnOnce(&[In]) -> Out,
-    Part2: FnOnce(&[In]) -> Out,
+    Part1: FnMut(&[In]) -> Out,
+    Part2: FnMut(&[In]) -> Out,
     Out: Display,
 {
-    let input = parse_input_lines(input_data, parser);
+    if let Some(runs) = bench::runs() {
+        let parse = || parse_input_lines(input_data, &mut parser);
+        bench::bench(package, runs, parse, |v| part1(v), |v| part2(v));
+        return;
+    }
 
-    let output1 = part1(&input);
-    println!("{output1}");
+    let input = parse_input_lines(input_data, parser);
 
-    let output2 = part2(&input);
-    println!("{output2}");
+    let outcome1 = report::part(package, 1, || part1(&input));
+    let outcome2 = report::part(package, 2, || part2(&input));
+    finish([outcome1, outcome2]);
 }
 
 pub fn run_alt<Parser, Part1, Part2, In, Out>(
+    package: &str,
     input_data: &'static str,
-    parser: Parser,
-    part1: Part1,
-    part2: Part2,
+    mut parser: Parser,
+    mut part1: Part1,
+    mut part2: Part2,
 ) where
-    Parser: FnOnce(&'static str) -> In,
-    Part1: FnOnce(&In) -> Out,
-    Part2: FnOnce(&In) -> Out,
+    Parser: FnMut(&'static str) -> In,
+    Part1: FnMut(&In) -> Out,
+    Part2: FnMut(&In) -> Out,
     Out: Display,
 {
+    if let Some(runs) = bench::runs() {
+        bench::bench(package, runs, || parser(input_data), &mut part1, &mut part2);
+        return;
+    }
+
     let input = parser(input_data);
 
-    let output1 = part1(&input);
-    println!("{output1}");
+    let outcome1 = report::part(package, 1, || part1(&input));
+    let outcome2 = report::part(package, 2, || part2(&input));
+    finish([outcome1, outcome2]);
+}
 
-    let output2 = part2(&input);
-    println!("{output2}");
+// failed parts were already reported, but should still fail the process
+fn finish(outcomes: [report::Outcome; 2]) {
+    use report::Outcome;
+    if outcomes.iter().any(|o| matches!(o, Outcome::TimedOut)) {
+        std::process::exit(progress::TIMED_OUT);
+    }
+    if outcomes.iter().any(|o| matches!(o, Outcome::Panicked(_))) {
+        std::process::exit(101);
+    }
 }
 
 pub fn test<Parser, Part, In, Out>(
@@ -186,19 +215,24 @@ where
 
 #[macro_export]
 macro_rules! register {
-    ($parser:expr, $part1:expr, $part2:expr) => {
-        $crate::register!($parser, $part1, $part2, run, test);
+    ($parser:expr, $part1:expr, $part2:expr, @alt $(, @gen $gen:expr)?) => {
+        $crate::register!($parser, $part1, $part2, run_alt, test_alt $(, $gen)?);
     };
 
-    ($parser:expr, $part1:expr, $part2:expr, @alt) => {
-        $crate::register!($parser, $part1, $part2, run_alt, test_alt);
+    ($parser:expr, $part1:expr, $part2:expr $(, @gen $gen:expr)?) => {
+        $crate::register!($parser, $part1, $part2, run, test $(, $gen)?);
     };
 
-    ($parser:expr, $part1:expr, $part2:expr, $run:ident, $test:ident) => {
+    ($parser:expr, $part1:expr, $part2:expr, $run:ident, $test:ident $(, $gen:expr)?) => {
         const INPUT: &str = include_str!("../input.txt");
 
         fn main() {
-            $crate::$run(INPUT, $parser, $part1, $part2);
+            let generator = None::<$crate::gen::Generator> $(.or(Some($gen)))?;
+            if $crate::gen::generate_from_env(generator) {
+                return;
+            }
+            let input = $crate::gen::input(INPUT);
+            $crate::$run(env!("CARGO_PKG_NAME"), input, $parser, $part1, $part2);
         }
 
         #[cfg(test)]
@@ -216,9 +250,14 @@ macro_rules! register {
         #[cfg(test)]
         #[test]
         fn test_part2() {
-            $crate::$test(TEST_INPUT, TEST_OUTPUT, $parser, $part2, true);
+            // part 2's own example, for puzzles that bring a new one; read at run time since
+            // most days don't have the file
+            let test2 = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/test2.txt"));
+            let input = match test2 {
+                Ok(test2) if !test2.is_empty() => test2.leak(),
+                _ => TEST_INPUT,
+            };
+            $crate::$test(input, TEST_OUTPUT, $parser, $part2, true);
         }
     };
 }
-
-// TODO: benchmarking
@@ -0,0 +1,126 @@
+//! Number theory for puzzles built from interlocking cycles.
+//!
+//! Everything works on `i64` and widens to `i128` for intermediate products, so moduli up to
+//! `i64::MAX` are fine.
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    ext_gcd(a, b).0
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) >= 0` and `a*x + b*y = g`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `x mod m`, in `0..m`.
+pub fn modulo(x: i64, m: i64) -> i64 {
+    x.rem_euclid(m)
+}
+
+fn mul_mod(a: i64, b: i64, m: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+/// The `x` in `0..m` with `a*x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(modulo(a, m), m);
+    (g == 1).then(|| modulo(x, m))
+}
+
+/// `base^exp mod m`, by repeated squaring.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut base = modulo(base, m);
+    let mut acc = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Chinese remainder theorem for `x ≡ r (mod m)` over every `(r, m)`, where the moduli need
+/// not be coprime. Returns `(x, lcm)` with `x` in `0..lcm`, or `None` if the congruences
+/// contradict each other. Panics if the combined modulus doesn't fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0, 1);
+    for (r, n) in congruences {
+        // x + m*k ≡ r (mod n)  <=>  m*k ≡ r - x (mod n)
+        let (g, inv, _) = ext_gcd(m, n);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = n / g;
+        let k = mul_mod(diff / g, inv, step);
+        let next = m as i128 * step as i128;
+        x = (x as i128 + m as i128 * k as i128).rem_euclid(next) as i64;
+        m = i64::try_from(next).expect("combined modulus overflows i64");
+    }
+    Some((x, m))
+}
+
+/// The first time at which every cycle fires, where a cycle `(offset, period)` fires at
+/// `offset`, `offset + period`, `offset + 2*period`, and so on.
+pub fn first_common_time(cycles: &[(i64, i64)]) -> Option<i64> {
+    let (x, m) = crt(cycles
+        .iter()
+        .map(|&(offset, period)| (modulo(offset, period), period)))?;
+    let start = cycles.iter().map(|c| c.0).max().unwrap_or(0);
+    if x >= start {
+        return Some(x);
+    }
+    // step forward whole periods of the combined cycle until every cycle has started
+    let behind = start - x;
+    Some(x + (behind + m - 1) / m * m)
+}
+
+#[cfg(test)]
+#[test]
+fn test_crt() {
+    assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+    assert_eq!(crt([(0, 4), (1, 6)]), None);
+    assert_eq!(crt([]), Some((0, 1)));
+}
+
+#[cfg(test)]
+#[test]
+fn test_modular() {
+    assert_eq!(ext_gcd(240, 46), (2, -9, 47));
+    assert_eq!(mod_inv(3, 11), Some(4));
+    assert_eq!(mod_inv(4, 8), None);
+    assert_eq!(mod_pow(2, 62, 1_000_000_007), 145586002);
+    assert_eq!(mod_pow(5, 0, 1), 0);
+}
+
+#[cfg(test)]
+#[test]
+fn test_first_common_time() {
+    assert_eq!(first_common_time(&[(2, 3), (4, 5)]), Some(14));
+    assert_eq!(first_common_time(&[(30, 3), (4, 5)]), Some(39));
+    assert_eq!(first_common_time(&[(3, 3), (5, 5)]), Some(15));
+    assert_eq!(first_common_time(&[(0, 2), (1, 2)]), None);
+}
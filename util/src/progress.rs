@@ -0,0 +1,161 @@
+//! Progress reporting for long loops, and the per-part timeout.
+//!
+//! A loop creates a [`Progress`] and feeds it counts (steps taken, nodes visited, ...). While
+//! stderr is a terminal, or `AOC_PROGRESS=1`, the count and rate are shown there a few times a
+//! second. `AOC_PROGRESS=0` turns it off.
+//!
+//! `AOC_TIMEOUT=seconds` gives every part a deadline. A part that feeds a [`Progress`] is
+//! unwound at its next update after the deadline, so the next part still runs. A part that
+//! never reports progress is stopped by a watchdog shortly after, which ends the process with
+//! [`TIMED_OUT`].
+
+use std::io::{IsTerminal, Write};
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub const PROGRESS_VAR: &str = "AOC_PROGRESS";
+pub const TIMEOUT_VAR: &str = "AOC_TIMEOUT";
+
+/// Exit code of a day binary whose watchdog fired, as with coreutils' `timeout`.
+pub const TIMED_OUT: i32 = 124;
+
+/// How long the watchdog gives a part to notice its deadline by itself.
+const GRACE: Duration = Duration::from_millis(500);
+
+const REDRAW: Duration = Duration::from_millis(250);
+
+/// Unwinding payload for a part stopped at its deadline.
+pub struct TimedOut;
+
+static DEADLINE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Whether progress is shown; stderr lines are overwritten in place on a terminal.
+fn mode() -> Option<bool> {
+    static MODE: OnceLock<Option<bool>> = OnceLock::new();
+    *MODE.get_or_init(|| {
+        let terminal = std::io::stderr().is_terminal();
+        match std::env::var(PROGRESS_VAR).as_deref() {
+            Ok("0") => None,
+            Ok(_) => Some(terminal),
+            Err(_) => terminal.then_some(true),
+        }
+    })
+}
+
+/// The per-part limit from `AOC_TIMEOUT`, if any.
+pub fn timeout() -> Option<Duration> {
+    static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+    *TIMEOUT.get_or_init(|| {
+        let secs = std::env::var(TIMEOUT_VAR).ok()?;
+        let secs = secs
+            .trim()
+            .parse::<f64>()
+            .expect("AOC_TIMEOUT should be seconds");
+        Some(Duration::from_secs_f64(secs))
+    })
+}
+
+/// Watches one part. `expired` runs on the watchdog thread if the part is still going
+/// [`GRACE`] after its deadline; dropping the guard stands the watchdog down.
+pub struct Watchdog {
+    _done: Option<mpsc::Sender<()>>,
+}
+
+impl Watchdog {
+    pub fn start(expired: impl FnOnce() + Send + 'static) -> Self {
+        let Some(timeout) = timeout() else {
+            return Self { _done: None };
+        };
+        *DEADLINE.lock().unwrap() = Some(Instant::now() + timeout);
+
+        let (done, wait) = mpsc::channel::<()>();
+        std::thread::spawn(move || {
+            if wait.recv_timeout(timeout + GRACE) == Err(mpsc::RecvTimeoutError::Timeout) {
+                expired();
+                std::process::exit(TIMED_OUT);
+            }
+        });
+        Self { _done: Some(done) }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        *DEADLINE.lock().unwrap() = None;
+    }
+}
+
+/// A running count for one loop. See the module docs.
+pub struct Progress {
+    what: &'static str,
+    count: u64,
+    start: Instant,
+    last_draw: Instant,
+    drawn: bool,
+    until_check: u32,
+}
+
+impl Progress {
+    /// `what` names the unit being counted, e.g. `"steps"`.
+    pub fn new(what: &'static str) -> Self {
+        Self {
+            what,
+            count: 0,
+            start: Instant::now(),
+            last_draw: Instant::now(),
+            drawn: false,
+            until_check: 0,
+        }
+    }
+
+    pub fn inc(&mut self) {
+        self.set(self.count + 1);
+    }
+
+    pub fn set(&mut self, count: u64) {
+        self.count = count;
+
+        // reading the clock on every update would slow down the tightest loops
+        if self.until_check > 0 {
+            self.until_check -= 1;
+            return;
+        }
+        self.until_check = 255;
+
+        let now = Instant::now();
+        if DEADLINE.lock().unwrap().is_some_and(|d| now >= d) {
+            self.clear();
+            std::panic::resume_unwind(Box::new(TimedOut));
+        }
+        if mode().is_some() && now - self.last_draw >= REDRAW {
+            self.last_draw = now;
+            self.drawn = true;
+            self.draw(now);
+        }
+    }
+
+    fn draw(&self, now: Instant) {
+        let elapsed = now - self.start;
+        let rate = self.count as f64 / elapsed.as_secs_f64().max(1e-9);
+        let line = format!("{} {} ({elapsed:.1?}, {rate:.0}/s)", self.count, self.what);
+
+        let mut stderr = std::io::stderr().lock();
+        let _ = match mode() {
+            Some(true) => write!(stderr, "\r\x1b[K{line}"),
+            _ => writeln!(stderr, "{line}"),
+        };
+    }
+
+    fn clear(&self) {
+        if mode() == Some(true) && self.drawn {
+            let _ = write!(std::io::stderr(), "\r\x1b[K");
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
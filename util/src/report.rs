@@ -0,0 +1,156 @@
+//! How a day binary reports its answers.
+//!
+//! By default each answer is printed on its own line. With `--json` on the command line, or
+//! `AOC_JSON` set to anything but `0`, each part instead prints one JSON object per line:
+//!
+//! ```text
+//! {"year":2023,"day":8,"part":1,"answer":"12345","duration_ms":1.234,"status":"ok"}
+//! {"year":2023,"day":8,"part":2,"answer":null,"duration_ms":0.056,"status":"panicked","message":"..."}
+//! ```
+//!
+//! Answers are always strings, so large numbers survive tools that parse JSON numbers as
+//! doubles. `status` is `ok`, `panicked`, or `timed_out` (see [`crate::progress`]); the runner
+//! also reports `timed_out` for parts cut off by its own, per-day timeout.
+
+use std::any::Any;
+use std::fmt::{Display, Write};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::progress::{TimedOut, Watchdog};
+
+pub const JSON_VAR: &str = "AOC_JSON";
+pub const JSON_FLAG: &str = "--json";
+
+pub fn json_enabled() -> bool {
+    static JSON: OnceLock<bool> = OnceLock::new();
+    *JSON.get_or_init(|| {
+        std::env::args().any(|a| a == JSON_FLAG) || std::env::var(JSON_VAR).is_ok_and(|v| v != "0")
+    })
+}
+
+/// The year and day from a package name like `y2023-day08`.
+pub fn puzzle(package: &str) -> Option<(u32, u32)> {
+    let (year, day) = package.strip_prefix('y')?.split_once("-day")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// The leading `"year":..,"day":..` fields of a JSON line, or just the package name as `day`
+/// for crates outside the `yYYYY-dayNN` scheme such as the template.
+pub fn id_fields(package: &str) -> String {
+    match puzzle(package) {
+        Some((year, day)) => format!(r#""year":{year},"day":{day}"#),
+        None => format!(r#""day":{}"#, quote(package)),
+    }
+}
+
+/// Quotes and escapes `s` as a JSON string.
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".into()
+    }
+}
+
+/// How a part ended.
+pub enum Outcome {
+    Ok(String),
+    Panicked(String),
+    TimedOut,
+}
+
+fn json_line(package: &str, part: u8, outcome: &Outcome, elapsed: Duration) -> String {
+    let ms = elapsed.as_secs_f64() * 1000.0;
+    let head = format!(r#"{{{},"part":{part}"#, id_fields(package));
+    match outcome {
+        Outcome::Ok(answer) => format!(
+            r#"{head},"answer":{},"duration_ms":{ms:.3},"status":"ok"}}"#,
+            quote(answer)
+        ),
+        Outcome::Panicked(msg) => format!(
+            r#"{head},"answer":null,"duration_ms":{ms:.3},"status":"panicked","message":{}}}"#,
+            quote(msg)
+        ),
+        Outcome::TimedOut => {
+            format!(r#"{head},"answer":null,"duration_ms":{ms:.3},"status":"timed_out"}}"#)
+        }
+    }
+}
+
+fn print(package: &str, part: u8, outcome: &Outcome, elapsed: Duration) {
+    if json_enabled() {
+        println!("{}", json_line(package, part, outcome, elapsed));
+        return;
+    }
+    match outcome {
+        Outcome::Ok(answer) => println!("{answer}"),
+        Outcome::Panicked(msg) => println!("panicked: {msg}"),
+        Outcome::TimedOut => println!("timed out"),
+    }
+}
+
+/// Runs one part under the `AOC_TIMEOUT` watchdog and prints how it went. Outside JSON mode
+/// a panic propagates as usual instead of being reported.
+pub fn part<Out: Display>(package: &str, part: u8, solve: impl FnOnce() -> Out) -> Outcome {
+    let start = Instant::now();
+    let watchdog = {
+        let package = package.to_owned();
+        Watchdog::start(move || print(&package, part, &Outcome::TimedOut, start.elapsed()))
+    };
+    let result = catch_unwind(AssertUnwindSafe(solve));
+    drop(watchdog);
+    let elapsed = start.elapsed();
+
+    let outcome = match result {
+        Ok(out) => Outcome::Ok(out.to_string()),
+        Err(e) if e.is::<TimedOut>() => Outcome::TimedOut,
+        Err(e) if json_enabled() => Outcome::Panicked(panic_message(&*e)),
+        Err(e) => resume_unwind(e),
+    };
+    print(package, part, &outcome, elapsed);
+    outcome
+}
+
+#[cfg(test)]
+#[test]
+fn test_json_line() {
+    let ok = json_line(
+        "y2023-day08",
+        1,
+        &Outcome::Ok("42".into()),
+        Duration::from_micros(1500),
+    );
+    assert_eq!(
+        ok,
+        r#"{"year":2023,"day":8,"part":1,"answer":"42","duration_ms":1.500,"status":"ok"}"#
+    );
+
+    let panicked = Outcome::Panicked("bad \"x\"\n".into());
+    let panicked = json_line("template", 2, &panicked, Duration::ZERO);
+    assert_eq!(
+        panicked,
+        r#"{"day":"template","part":2,"answer":null,"duration_ms":0.000,"status":"panicked","message":"bad \"x\"\n"}"#
+    );
+}
@@ -0,0 +1,93 @@
+use std::fmt::Arguments;
+use std::io::Write;
+use std::sync::OnceLock;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    fn from_name(s: &str) -> Option<Self> {
+        let level = match s.trim().to_ascii_lowercase().as_str() {
+            "error" | "1" => Level::Error,
+            "warn" | "2" => Level::Warn,
+            "info" | "3" => Level::Info,
+            "debug" | "4" => Level::Debug,
+            "trace" | "5" => Level::Trace,
+            _ => return None,
+        };
+        Some(level)
+    }
+}
+
+/// Name of the environment variable holding the maximum level to emit, e.g. `AOC_LOG=debug`.
+pub const ENV_VAR: &str = "AOC_LOG";
+
+/// The maximum enabled level, or `None` if tracing is off (the default).
+pub fn max_level() -> Option<Level> {
+    static LEVEL: OnceLock<Option<Level>> = OnceLock::new();
+    *LEVEL.get_or_init(|| {
+        let var = std::env::var(ENV_VAR).ok()?;
+        Level::from_name(&var)
+    })
+}
+
+pub fn enabled(level: Level) -> bool {
+    max_level().is_some_and(|max| level <= max)
+}
+
+/// Writes a message to stderr, keeping stdout free for answers.
+/// Use the `error!`..`trace!` macros rather than calling this directly.
+pub fn write(level: Level, args: Arguments) {
+    let mut stderr = std::io::stderr().lock();
+    let _ = writeln!(stderr, "[{}] {args}", level.name());
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:ident, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::write($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!(Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!(Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!(Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!(Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!(Trace, $($arg)+) };
+}